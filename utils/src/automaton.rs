@@ -0,0 +1,213 @@
+use crate::{
+    grid::Grid,
+    math::{Neighborhood, Vec2D},
+    sparse_grid::SparseGrid,
+};
+
+/// Double buffered cellular automaton on a [Grid]. In each step, every cell is replaced by
+/// `rule(current cell, neighbor values)`. Cells outside of the grid are not passed to the rule.
+pub struct Automaton<T, F> {
+    grid: Grid<T>,
+    buffer: Grid<T>,
+    neighborhood: Neighborhood,
+    rule: F,
+    steps: usize,
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &[&T]) -> T,
+{
+    pub fn new(grid: Grid<T>, neighborhood: Neighborhood, rule: F) -> Self {
+        Automaton { buffer: grid.clone(), grid, neighborhood, rule, steps: 0 }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Number of steps run so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn step(&mut self) {
+        let mut neighbors = Vec::with_capacity(8);
+        for ((pos, cell), next) in self.grid.iter().zip(self.buffer.inner_mut()) {
+            neighbors.clear();
+            neighbors.extend(pos.neighbors(self.neighborhood).filter_map(|n| self.grid.get(n)));
+            *next = (self.rule)(cell, &neighbors);
+        }
+        std::mem::swap(&mut self.grid, &mut self.buffer);
+        self.steps += 1;
+    }
+
+    pub fn run(&mut self, steps: usize) -> &Grid<T> {
+        for _ in 0..steps {
+            self.step();
+        }
+        &self.grid
+    }
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + PartialEq,
+    F: Fn(&T, &[&T]) -> T,
+{
+    /// Step until the grid doesn't change anymore. Returns the number of steps that changed the
+    /// grid, i.e. not counting the final step that confirmed the fixpoint.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut changed_steps = 0;
+        loop {
+            self.step();
+            if self.grid == self.buffer {
+                return changed_steps;
+            }
+            changed_steps += 1;
+        }
+    }
+}
+
+/// Double buffered cellular automaton on an unbounded [SparseGrid]. Missing cells are passed to
+/// the rule as `None`, and returning `None` removes a cell. Only cells that are present or have a
+/// present neighbor are evaluated, so `rule(None, &[])` must be `None`.
+pub struct SparseAutomaton<T, F> {
+    grid: SparseGrid<T>,
+    buffer: SparseGrid<T>,
+    neighborhood: Neighborhood,
+    rule: F,
+    steps: usize,
+}
+
+impl<T, F> SparseAutomaton<T, F>
+where
+    T: std::hash::Hash,
+    F: Fn(Option<&T>, &[&T]) -> Option<T>,
+{
+    pub fn new(grid: SparseGrid<T>, neighborhood: Neighborhood, rule: F) -> Self {
+        SparseAutomaton { grid, buffer: SparseGrid::new(), neighborhood, rule, steps: 0 }
+    }
+
+    pub fn grid(&self) -> &SparseGrid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> SparseGrid<T> {
+        self.grid
+    }
+
+    /// Number of steps run so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn step(&mut self) {
+        let candidates: std::collections::HashSet<Vec2D> = self
+            .grid
+            .keys()
+            .flat_map(|pos| pos.neighbors(self.neighborhood).chain(std::iter::once(*pos)))
+            .collect();
+
+        let mut neighbors = Vec::with_capacity(8);
+        self.buffer.clear();
+        for pos in candidates {
+            neighbors.clear();
+            neighbors.extend(pos.neighbors(self.neighborhood).filter_map(|n| self.grid.get(n)));
+            if let Some(next) = (self.rule)(self.grid.get(pos), &neighbors) {
+                self.buffer.insert(pos, next);
+            }
+        }
+        std::mem::swap(&mut self.grid, &mut self.buffer);
+        self.steps += 1;
+    }
+
+    pub fn run(&mut self, steps: usize) -> &SparseGrid<T> {
+        for _ in 0..steps {
+            self.step();
+        }
+        &self.grid
+    }
+}
+
+impl<T, F> SparseAutomaton<T, F>
+where
+    T: std::hash::Hash + PartialEq,
+    F: Fn(Option<&T>, &[&T]) -> Option<T>,
+{
+    /// Step until the grid doesn't change anymore. Returns the number of steps that changed the
+    /// grid, i.e. not counting the final step that confirmed the fixpoint.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut changed_steps = 0;
+        loop {
+            self.step();
+            if self.grid == self.buffer {
+                return changed_steps;
+            }
+            changed_steps += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn life(alive: &bool, neighbors: &[&bool]) -> bool {
+        let alive_neighbors = neighbors.iter().filter(|&&&n| n).count();
+        alive_neighbors == 3 || (*alive && alive_neighbors == 2)
+    }
+
+    #[test]
+    fn grid_life() {
+        let blinker: Grid<_> = [[false, true, false]; 3].into();
+        let mut automaton = Automaton::new(blinker.clone(), Neighborhood::All, life);
+        assert_eq!(automaton.run(1), &Grid::from([[false; 3], [true; 3], [false; 3]]));
+        assert_eq!(automaton.run(1), &blinker);
+        assert_eq!(automaton.steps(), 2);
+    }
+
+    #[test]
+    fn grid_fixpoint() {
+        let mut grid = Grid::with_value(0, 3, 3);
+        grid[(1, 1)] = 1;
+        let spread =
+            |cell: &i32, neighbors: &[&i32]| *neighbors.iter().copied().max().unwrap().max(cell);
+
+        let mut automaton = Automaton::new(grid.clone(), Neighborhood::Orthogonal, spread);
+        assert_eq!(automaton.run_until_stable(), 2);
+        assert_eq!(automaton.grid(), &Grid::with_value(1, 3, 3));
+
+        let mut automaton = Automaton::new(grid, Neighborhood::All, spread);
+        assert_eq!(automaton.run_until_stable(), 1);
+    }
+
+    #[test]
+    fn sparse_life() {
+        let sparse_life = |cell: Option<&()>, neighbors: &[&()]| {
+            life(&cell.is_some(), &vec![&true; neighbors.len()]).then_some(())
+        };
+
+        let glider: SparseGrid<()> =
+            [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].into_iter().map(|pos| (pos, ())).collect();
+        let mut automaton = SparseAutomaton::new(glider.clone(), Neighborhood::All, sparse_life);
+        automaton.run(4);
+        assert_eq!(
+            automaton.grid().keys().copied().collect::<HashSet<_>>(),
+            glider.keys().map(|&pos| pos + (1, 1)).collect()
+        );
+
+        let block: SparseGrid<()> =
+            [(0, 0), (1, 0), (0, 1), (1, 1)].into_iter().map(|pos| (pos, ())).collect();
+        let mut automaton = SparseAutomaton::new(block.clone(), Neighborhood::All, sparse_life);
+        assert_eq!(automaton.run_until_stable(), 0);
+        assert_eq!(automaton.into_grid(), block);
+    }
+}
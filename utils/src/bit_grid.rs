@@ -0,0 +1,388 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use crate::{grid::Grid, math::Vec2D};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Grid of booleans, stored as one bit per cell. Each row is a sequence of u64 words, with column
+/// `x` at bit `x % 64` of word `x / 64`. Bits past the last column are always zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl BitGrid {
+    pub fn new(num_rows: usize, num_cols: usize) -> Self {
+        let words_per_row = num_cols.div_ceil(WORD_BITS);
+        BitGrid { words: vec![0; num_rows * words_per_row], words_per_row, num_rows, num_cols }
+    }
+
+    /// Cells equal to `on` are set
+    pub fn from_char_grid(grid: &Grid<char>, on: char) -> Self {
+        Self::from_grid(grid, |&c| c == on)
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, is_set: impl Fn(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.num_rows(), grid.num_cols());
+        for (pos, _) in grid.iter().filter(|(_, value)| is_set(value)) {
+            bits.set(pos, true);
+        }
+        bits
+    }
+
+    pub fn to_char_grid(&self, on: char, off: char) -> Grid<char> {
+        self.to_grid(|set| if set { on } else { off })
+    }
+
+    pub fn to_grid<T>(&self, f: impl Fn(bool) -> T) -> Grid<T> {
+        let f = &f;
+        (0..self.num_rows)
+            .map(|y| (0..self.num_cols).map(move |x| f(self.word(x, y) & Self::mask(x) != 0)))
+            .collect()
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn contains(&self, pos: &Vec2D) -> bool {
+        pos.x >= 0
+            && pos.y >= 0
+            && (pos.x as usize) < self.num_cols
+            && (pos.y as usize) < self.num_rows
+    }
+
+    fn word(&self, x: usize, y: usize) -> u64 {
+        self.words[y * self.words_per_row + x / WORD_BITS]
+    }
+
+    fn mask(x: usize) -> u64 {
+        1 << (x % WORD_BITS)
+    }
+
+    pub fn get(&self, pos: impl Into<Vec2D>) -> Option<bool> {
+        let pos = pos.into();
+        self.contains(&pos).then(|| {
+            let (x, y) = (pos.x as usize, pos.y as usize);
+            self.word(x, y) & Self::mask(x) != 0
+        })
+    }
+
+    /// Panics if `pos` is outside the grid
+    pub fn set(&mut self, pos: impl Into<Vec2D>, value: bool) {
+        let pos = pos.into();
+        assert!(self.contains(&pos), "{pos:?} is outside the grid");
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        let word = &mut self.words[y * self.words_per_row + x / WORD_BITS];
+        if value {
+            *word |= Self::mask(x);
+        } else {
+            *word &= !Self::mask(x);
+        }
+    }
+
+    /// Number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Positions of all set cells, in row-major order
+    pub fn ones(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        self.words.chunks_exact(self.words_per_row.max(1)).enumerate().flat_map(|(y, row)| {
+            row.iter().enumerate().flat_map(move |(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    (word != 0).then(|| {
+                        let bit = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        Vec2D::new((i * WORD_BITS + bit) as i64, y as i64)
+                    })
+                })
+            })
+        })
+    }
+
+    /// Moves all cells `dy` rows down (up if negative). Cells moved past the edge are dropped, and
+    /// vacated rows are cleared.
+    pub fn shift_rows(&mut self, dy: i64) {
+        let offset = dy.unsigned_abs() as usize * self.words_per_row;
+        let len = self.words.len();
+        if offset >= len {
+            self.words.fill(0);
+        } else if dy > 0 {
+            self.words.copy_within(..len - offset, offset);
+            self.words[..offset].fill(0);
+        } else {
+            self.words.copy_within(offset.., 0);
+            self.words[len - offset..].fill(0);
+        }
+    }
+
+    /// Moves all cells `dx` columns to the right (left if negative). Cells moved past the edge are
+    /// dropped, and vacated columns are cleared.
+    pub fn shift_cols(&mut self, dx: i64) {
+        let (word_shift, bit_shift) =
+            (dx.unsigned_abs() as usize / WORD_BITS, dx.unsigned_abs() as usize % WORD_BITS);
+        let words_per_row = self.words_per_row;
+        for row in self.words.chunks_exact_mut(words_per_row.max(1)) {
+            let old = row.to_vec();
+            let at = |i: Option<usize>| i.and_then(|i| old.get(i)).copied().unwrap_or(0);
+            for (i, word) in row.iter_mut().enumerate() {
+                *word = if dx >= 0 {
+                    let near = at(i.checked_sub(word_shift));
+                    let far = at(i.checked_sub(word_shift + 1));
+                    match bit_shift {
+                        0 => near,
+                        _ => near << bit_shift | far >> (WORD_BITS - bit_shift),
+                    }
+                } else {
+                    let near = at(Some(i + word_shift));
+                    let far = at(Some(i + word_shift + 1));
+                    match bit_shift {
+                        0 => near,
+                        _ => near >> bit_shift | far << (WORD_BITS - bit_shift),
+                    }
+                };
+            }
+        }
+        self.clear_padding();
+    }
+
+    /// Copy with all cells moved by `offset`, see [BitGrid::shift_cols] and [BitGrid::shift_rows]
+    pub fn shifted(&self, offset: impl Into<Vec2D>) -> Self {
+        let offset = offset.into();
+        let mut shifted = self.clone();
+        shifted.shift_rows(offset.y);
+        shifted.shift_cols(offset.x);
+        shifted
+    }
+
+    fn clear_padding(&mut self) {
+        let used_bits = self.num_cols % WORD_BITS;
+        if used_bits == 0 {
+            return;
+        }
+        let last_word_mask = (1 << used_bits) - 1;
+        for row in self.words.chunks_exact_mut(self.words_per_row) {
+            *row.last_mut().unwrap() &= last_word_mask;
+        }
+    }
+
+    /// Number of set cells among the 8 neighbors, in 4 bit planes (least significant first)
+    fn neighbor_count_planes(&self) -> [Vec<u64>; 4] {
+        let mut planes = std::array::from_fn(|_| vec![0; self.words.len()]);
+        for offset in Vec2D::zero().all_neighbors() {
+            let neighbor = self.shifted(offset);
+            for (i, mut carry) in neighbor.words.into_iter().enumerate() {
+                for plane in planes.iter_mut() {
+                    let sum = plane[i] ^ carry;
+                    carry &= plane[i];
+                    plane[i] = sum;
+                }
+            }
+        }
+        planes
+    }
+
+    /// Number of set cells among the 8 neighbors of each cell
+    pub fn neighbor_counts(&self) -> Grid<u8> {
+        let planes = self.neighbor_count_planes();
+        let planes = &planes;
+        (0..self.num_rows)
+            .map(|y| {
+                (0..self.num_cols).map(move |x| {
+                    let i = y * self.words_per_row + x / WORD_BITS;
+                    planes.iter().rev().fold(0, |count, plane| {
+                        count << 1 | u8::from(plane[i] & Self::mask(x) != 0)
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// One step of a Life-like automaton: Empty cells become set if their number of set neighbors
+    /// is in `birth`, set cells stay set if it is in `survival`. Conway's Game of Life is
+    /// `life_step(&[3], &[2, 3])`.
+    pub fn life_step(&self, birth: &[u8], survival: &[u8]) -> Self {
+        let planes = self.neighbor_count_planes();
+        let count_is = |i: usize, count: u8| {
+            planes.iter().enumerate().fold(!0, |matches, (bit, plane)| {
+                matches & if count >> bit & 1 == 1 { plane[i] } else { !plane[i] }
+            })
+        };
+
+        let mut next = self.clone();
+        for (i, word) in next.words.iter_mut().enumerate() {
+            let born = birth.iter().fold(0, |acc, &count| acc | count_is(i, count));
+            let survives = survival.iter().fold(0, |acc, &count| acc | count_is(i, count));
+            *word = !*word & born | *word & survives;
+        }
+        next.clear_padding();
+        next
+    }
+
+    fn zip_with(&mut self, other: &BitGrid, f: impl Fn(&mut u64, u64)) {
+        assert_eq!(
+            (self.num_rows, self.num_cols),
+            (other.num_rows, other.num_cols),
+            "grids must have the same size"
+        );
+        self.words.iter_mut().zip(&other.words).for_each(|(lhs, &rhs)| f(lhs, rhs));
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident) => {
+        impl $assign<&BitGrid> for BitGrid {
+            fn $assign_fn(&mut self, rhs: &BitGrid) {
+                self.zip_with(rhs, |lhs, rhs| lhs.$assign_fn(rhs));
+            }
+        }
+
+        impl $op<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+
+            fn $op_fn(self, rhs: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$assign_fn(rhs);
+                result
+            }
+        }
+    };
+}
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        result.words.iter_mut().for_each(|word| *word = !*word);
+        result.clear_padding();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::Input;
+
+    use super::*;
+
+    fn wide_grid() -> Grid<char> {
+        Grid::new(
+            (0..5)
+                .map(|y| {
+                    (0..130).map(|x| if (x * 7 + y * 3) % 5 == 0 { '#' } else { '.' }).collect()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn get_set() {
+        let grid = wide_grid();
+        let mut bits = BitGrid::from_char_grid(&grid, '#');
+        assert_eq!(bits.to_char_grid('#', '.'), grid);
+        assert_eq!(bits.count_ones(), grid.iter().filter(|(_, &c)| c == '#').count());
+        assert_eq!(
+            bits.ones().collect_vec(),
+            grid.iter().filter(|(_, &c)| c == '#').map(|(pos, _)| pos).collect_vec()
+        );
+
+        assert_eq!(bits.get((65, 1)), Some(false));
+        bits.set((65, 1), true);
+        assert_eq!(bits.get((65, 1)), Some(true));
+        bits.set((65, 1), false);
+        assert_eq!(bits.get((65, 1)), Some(false));
+        assert_eq!(bits.get((130, 0)), None);
+        assert_eq!(bits.get((0, -1)), None);
+    }
+
+    #[test]
+    fn shifts() {
+        let grid = wide_grid();
+        let bits = BitGrid::from_char_grid(&grid, '#');
+        for offset in [(1, 0), (-1, 0), (63, 1), (-64, -2), (65, 3), (-129, 0), (0, 5), (200, 0)] {
+            let shifted = bits.shifted(offset);
+            let expected = Grid::new(
+                (0..5)
+                    .map(|y| {
+                        (0..130)
+                            .map(|x| grid.get(Vec2D::new(x, y) - offset).copied().unwrap_or('.'))
+                            .collect()
+                    })
+                    .collect(),
+            );
+            assert_eq!(shifted.to_char_grid('#', '.'), expected, "{offset:?}");
+        }
+    }
+
+    #[test]
+    fn bit_ops() {
+        let lhs = BitGrid::from_char_grid(&Input::from("##..\n#.#.").char_grid(), '#');
+        let rhs = BitGrid::from_char_grid(&Input::from("#.#.\n.##.").char_grid(), '#');
+        assert_eq!((&lhs & &rhs).to_char_grid('#', '.'), Input::from("#...\n..#.").char_grid());
+        assert_eq!((&lhs | &rhs).to_char_grid('#', '.'), Input::from("###.\n###.").char_grid());
+        assert_eq!((&lhs ^ &rhs).to_char_grid('#', '.'), Input::from(".##.\n##..").char_grid());
+        assert_eq!((!&lhs).to_char_grid('#', '.'), Input::from("..##\n.#.#").char_grid());
+    }
+
+    #[test]
+    fn life() {
+        let grid = wide_grid();
+        let bits = BitGrid::from_char_grid(&grid, '#');
+        let expected = grid
+            .map(|_| 0)
+            .iter()
+            .map(|(pos, _)| grid.all_neighbor_values(&pos).filter(|&&c| c == '#').count() as u8)
+            .collect_vec();
+        assert_eq!(bits.neighbor_counts().into_inner(), expected);
+
+        let glider = Input::from(
+            "
+.#....
+..#...
+###...
+......
+......",
+        )
+        .char_grid();
+        let moved = Input::from(
+            "
+......
+..#...
+...#..
+.###..
+......",
+        )
+        .char_grid();
+        let mut life = BitGrid::from_char_grid(&glider, '#');
+        for _ in 0..4 {
+            life = life.life_step(&[3], &[2, 3]);
+        }
+        assert_eq!(life.to_char_grid('#', '.'), moved);
+    }
+
+    #[test]
+    fn empty_shapes() {
+        for (num_rows, num_cols) in [(3, 0), (1, 0), (0, 0)] {
+            let bits = BitGrid::new(num_rows, num_cols);
+            let grid = bits.to_char_grid('#', '.');
+            assert_eq!((grid.num_rows(), grid.num_cols()), (num_rows, num_cols));
+            let counts = bits.neighbor_counts();
+            assert_eq!((counts.num_rows(), counts.num_cols()), (num_rows, num_cols));
+        }
+    }
+}
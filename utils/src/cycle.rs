@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence of states x0, x1 = step(x0), ... where x(mu) is the first state that repeats, and
+/// x(mu + lambda) == x(mu)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that is part of the cycle
+    pub mu: usize,
+    /// Length of the cycle
+    pub lambda: usize,
+}
+
+impl Cycle {
+    /// Smallest step index whose state equals the state at step `n`
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+}
+
+/// Find the cycle by remembering all states seen so far
+pub fn find_cycle<S: Hash + Eq + Clone>(initial: S, step: impl Fn(&S) -> S) -> Cycle {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for i in 0.. {
+        if let Some(&mu) = seen.get(&state) {
+            return Cycle { mu, lambda: i - mu };
+        }
+        let next = step(&state);
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// Find the cycle with Brent's algorithm. Only needs to keep two states in memory, but calls
+/// `step` about three times as often as [find_cycle].
+pub fn find_cycle_brent<S: Eq + Clone>(initial: S, step: impl Fn(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    Cycle { mu, lambda }
+}
+
+/// The state after `n` steps, skipping ahead as soon as a cycle is found
+pub fn state_at<S: Hash + Eq + Clone>(initial: S, step: impl Fn(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    for i in 0.. {
+        if i == n {
+            return state;
+        }
+        if let Some(&mu) = seen.get(&state) {
+            let cycle = Cycle { mu, lambda: i - mu };
+            return states.swap_remove(cycle.equivalent_step(n));
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        states.push(state);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// Shortcut for `score(&state_at(initial, step, n))`
+pub fn score_at<S: Hash + Eq + Clone, V>(
+    initial: S,
+    step: impl Fn(&S) -> S,
+    score: impl Fn(&S) -> V,
+    n: usize,
+) -> V {
+    score(&state_at(initial, step, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grid::Grid, sparse_grid::SparseGrid};
+
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, 2, ...
+    fn step(x: &usize) -> usize {
+        if *x < 5 {
+            x + 1
+        } else {
+            2
+        }
+    }
+
+    #[test]
+    fn cycle() {
+        assert_eq!(find_cycle(0, step), Cycle { mu: 2, lambda: 4 });
+        assert_eq!(find_cycle_brent(0, step), Cycle { mu: 2, lambda: 4 });
+        assert_eq!(find_cycle(3, step), Cycle { mu: 0, lambda: 4 });
+        assert_eq!(find_cycle_brent(3, step), Cycle { mu: 0, lambda: 4 });
+
+        assert_eq!(state_at(0, step, 0), 0);
+        assert_eq!(state_at(0, step, 5), 5);
+        assert_eq!(state_at(0, step, 6), 2);
+        assert_eq!(state_at(0, step, 1_000_000_000), 4);
+        assert_eq!(score_at(0, step, |x| x * 10, 1_000_000_001), 50);
+    }
+
+    #[test]
+    fn grids() {
+        let grid: Grid<_> = [[1, 2], [3, 4]].into();
+        assert_eq!(find_cycle(grid.clone(), Grid::rotated_right), Cycle { mu: 0, lambda: 4 });
+        assert_eq!(
+            state_at(grid.clone(), Grid::rotated_right, 1_000_000_001),
+            grid.rotated_right()
+        );
+
+        // Moves right until x == 3, then stays there
+        let sparse: SparseGrid<_> = [((0, 0), 'a'), ((0, 1), 'b')].into_iter().collect();
+        let step = |grid: &SparseGrid<char>| {
+            grid.iter().map(|(pos, c)| (*pos + (i64::from(pos.x < 3), 0), *c)).collect()
+        };
+        assert_eq!(find_cycle(sparse.clone(), step), Cycle { mu: 3, lambda: 1 });
+        assert_eq!(find_cycle_brent(sparse.clone(), step), Cycle { mu: 3, lambda: 1 });
+        assert_eq!(
+            state_at(sparse, step, 1_000_000_000),
+            [((3, 0), 'a'), ((3, 1), 'b')].into_iter().collect()
+        );
+    }
+}
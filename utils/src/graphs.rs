@@ -1,9 +1,11 @@
 use std::cmp::Reverse;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
 
 use priority_queue::PriorityQueue;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 pub type Cost = usize;
 
@@ -30,7 +32,7 @@ pub fn bfs<N: Node>(
     end: impl Into<N>,
 ) -> BfsResult<N> {
     let end = end.into();
-    bfs_impl(graph, start, |node| *node == end)
+    bfs_impl(bfs_layers(graph, start), |node| *node == end)
 }
 
 pub fn bfs_predicate<N: Node>(
@@ -38,46 +40,338 @@ pub fn bfs_predicate<N: Node>(
     start: impl Into<N>,
     reached_target: impl Fn(&N) -> bool,
 ) -> BfsResult<N> {
-    bfs_impl(graph, start, reached_target)
+    bfs_impl(bfs_layers(graph, start), reached_target)
 }
 
 pub fn floodfill<N: Node>(
     graph: &impl UnweightedGraph<Node = N>,
     start: impl Into<N>,
 ) -> HashMap<N, usize> {
-    bfs_impl(graph, start, |_| false).visited
+    bfs_impl(bfs_layers(graph, start), |_| false).visited
 }
 
-fn bfs_impl<N: Node>(
+/// Like [bfs], but starting from all `start_nodes` at once
+pub fn bfs_from<N: Node, S: Into<N>>(
     graph: &impl UnweightedGraph<Node = N>,
+    start_nodes: impl IntoIterator<Item = S>,
+    end: impl Into<N>,
+) -> BfsResult<N> {
+    let end = end.into();
+    bfs_impl(bfs_layers_from(graph, start_nodes), |node| *node == end)
+}
+
+/// Like [bfs_predicate], but starting from all `start_nodes` at once
+pub fn bfs_predicate_from<N: Node, S: Into<N>>(
+    graph: &impl UnweightedGraph<Node = N>,
+    start_nodes: impl IntoIterator<Item = S>,
+    reached_target: impl Fn(&N) -> bool,
+) -> BfsResult<N> {
+    bfs_impl(bfs_layers_from(graph, start_nodes), reached_target)
+}
+
+/// Like [floodfill], but starting from all `start_nodes` at once
+pub fn floodfill_from<N: Node, S: Into<N>>(
+    graph: &impl UnweightedGraph<Node = N>,
+    start_nodes: impl IntoIterator<Item = S>,
+) -> HashMap<N, usize> {
+    bfs_impl(bfs_layers_from(graph, start_nodes), |_| false).visited
+}
+
+/// Same as [bfs], but expands each frontier in parallel
+#[cfg(feature = "rayon")]
+pub fn par_bfs<N: Node + Send + Sync>(
+    graph: &(impl UnweightedGraph<Node = N> + Sync),
+    start: impl Into<N>,
+    end: impl Into<N>,
+) -> BfsResult<N> {
+    let end = end.into();
+    bfs_impl(bfs_layers(graph, start).parallel(), |node| *node == end)
+}
+
+/// Same as [bfs_predicate], but expands each frontier in parallel
+#[cfg(feature = "rayon")]
+pub fn par_bfs_predicate<N: Node + Send + Sync>(
+    graph: &(impl UnweightedGraph<Node = N> + Sync),
     start: impl Into<N>,
     reached_target: impl Fn(&N) -> bool,
 ) -> BfsResult<N> {
+    bfs_impl(bfs_layers(graph, start).parallel(), reached_target)
+}
+
+/// Same as [floodfill], but expands each frontier in parallel
+#[cfg(feature = "rayon")]
+pub fn par_floodfill<N: Node + Send + Sync>(
+    graph: &(impl UnweightedGraph<Node = N> + Sync),
+    start: impl Into<N>,
+) -> HashMap<N, usize> {
+    bfs_impl(bfs_layers(graph, start).parallel(), |_| false).visited
+}
+
+fn bfs_impl<G: UnweightedGraph>(
+    mut layers: BfsLayers<'_, G>,
+    reached_target: impl Fn(&G::Node) -> bool,
+) -> BfsResult<G::Node> {
+    while let Some((distance, layer)) = layers.next() {
+        if layer.iter().any(&reached_target) {
+            return BfsResult { distance: Some(distance), visited: layers.into_visited() };
+        }
+    }
+    BfsResult { distance: None, visited: layers.into_visited() }
+}
+
+/// Iterator over the frontiers of a BFS, yielding each layer together with its distance from the
+/// start. Every node appears in exactly one layer.
+pub struct BfsLayers<'a, G: UnweightedGraph> {
+    graph: &'a G,
+    expand: ExpandFn<G>,
+    frontier: HashSet<G::Node>,
+    /// The last yielded layer, expanded only when the next layer is requested so that searches
+    /// stopping at a layer never look at the neighbors of its nodes
+    unexpanded: Option<HashSet<G::Node>>,
+    distance: usize,
+    max_depth: Option<usize>,
+    visited: HashMap<G::Node, usize>,
+}
+
+pub fn bfs_layers<G: UnweightedGraph>(graph: &G, start: impl Into<G::Node>) -> BfsLayers<'_, G> {
+    bfs_layers_from(graph, [start])
+}
+
+/// Like [bfs_layers], but with all `start_nodes` in the first layer
+pub fn bfs_layers_from<G: UnweightedGraph, S: Into<G::Node>>(
+    graph: &G,
+    start_nodes: impl IntoIterator<Item = S>,
+) -> BfsLayers<'_, G> {
+    BfsLayers {
+        graph,
+        expand: expand::<G>,
+        frontier: start_nodes.into_iter().map(Into::into).collect(),
+        unexpanded: None,
+        distance: 0,
+        max_depth: None,
+        visited: HashMap::new(),
+    }
+}
+
+impl<G: UnweightedGraph> BfsLayers<'_, G> {
+    /// Stop after yielding the layer at distance `max_depth`
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// All nodes of the layers yielded so far, with their distance
+    pub fn visited(&self) -> &HashMap<G::Node, usize> {
+        &self.visited
+    }
+
+    pub fn into_visited(self) -> HashMap<G::Node, usize> {
+        self.visited
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<G: UnweightedGraph + Sync> BfsLayers<'_, G>
+where
+    G::Node: Send + Sync,
+{
+    /// Expand each frontier in parallel
+    pub fn parallel(mut self) -> Self {
+        self.expand = par_expand::<G>;
+        self
+    }
+}
+
+/// Computes the next frontier from the current layer and all visited nodes. Stored as a function
+/// pointer so that the parallel version can be selected where its extra bounds are known.
+type ExpandFn<G> = fn(
+    &G,
+    &HashSet<<G as UnweightedGraph>::Node>,
+    &HashMap<<G as UnweightedGraph>::Node, usize>,
+) -> HashSet<<G as UnweightedGraph>::Node>;
+
+fn expand<G: UnweightedGraph>(
+    graph: &G,
+    layer: &HashSet<G::Node>,
+    visited: &HashMap<G::Node, usize>,
+) -> HashSet<G::Node> {
+    layer
+        .iter()
+        .flat_map(|node| graph.neighbors(node))
+        .filter(|neighbor| !visited.contains_key(neighbor))
+        .collect()
+}
+
+#[cfg(feature = "rayon")]
+fn par_expand<G: UnweightedGraph + Sync>(
+    graph: &G,
+    layer: &HashSet<G::Node>,
+    visited: &HashMap<G::Node, usize>,
+) -> HashSet<G::Node>
+where
+    G::Node: Send + Sync,
+{
+    layer
+        .par_iter()
+        .flat_map_iter(|node| graph.neighbors(node))
+        .filter(|neighbor| !visited.contains_key(neighbor))
+        .collect()
+}
+
+impl<G: UnweightedGraph> Iterator for BfsLayers<'_, G> {
+    type Item = (usize, HashSet<G::Node>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(layer) = self.unexpanded.take() {
+            self.frontier = (self.expand)(self.graph, &layer, &self.visited);
+        }
+        if self.frontier.is_empty() || self.max_depth.is_some_and(|max| self.distance > max) {
+            return None;
+        }
+
+        let distance = self.distance;
+        let layer = std::mem::take(&mut self.frontier);
+        for node in &layer {
+            self.visited.insert(node.clone(), distance);
+        }
+
+        if self.max_depth.is_none_or(|max| distance < max) {
+            self.unexpanded = Some(layer.clone());
+        }
+        self.distance += 1;
+
+        Some((distance, layer))
+    }
+}
+
+/// All nodes that can be reached in exactly `steps` steps, when walking back and forth between
+/// two nodes is allowed. Only correct for undirected, bipartite graphs (such as grids with
+/// orthogonal neighbors), where this is the case for every node with distance <= steps and
+/// matching parity. A start without neighbors can only be reached in 0 steps.
+pub fn reachable_in_exactly<N: Node>(
+    graph: &impl UnweightedGraph<Node = N>,
+    start: impl Into<N>,
+    steps: usize,
+) -> HashSet<N> {
     let start = start.into();
+    if steps > 0 && graph.neighbors(&start).next().is_none() {
+        return HashSet::new();
+    }
+    bfs_layers(graph, start)
+        .max_depth(steps)
+        .filter(|(distance, _)| distance % 2 == steps % 2)
+        .flat_map(|(_, layer)| layer)
+        .collect()
+}
 
-    let mut distance = 0;
-    let mut visited = HashMap::new();
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NearestSource<N> {
+    Unique(N),
+    /// Two or more sources have the same distance to this node
+    Tie,
+}
 
-    let mut next = HashSet::new();
-    next.insert(start);
+impl<N> NearestSource<N> {
+    pub fn unique(&self) -> Option<&N> {
+        match self {
+            NearestSource::Unique(source) => Some(source),
+            NearestSource::Tie => None,
+        }
+    }
 
-    loop {
-        if next.is_empty() {
-            return BfsResult { distance: None, visited };
+    fn merge(self, other: Self) -> Self
+    where
+        N: PartialEq,
+    {
+        if self == other {
+            self
+        } else {
+            NearestSource::Tie
         }
-        let mut neighbors = HashSet::new();
-        for node in next {
-            visited.insert(node.clone(), distance);
+    }
+}
 
-            if reached_target(&node) {
-                return BfsResult { distance: Some(distance), visited };
-            }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourceDistance<N> {
+    pub source: NearestSource<N>,
+    pub distance: usize,
+}
+
+/// BFS starting from all `sources` at once, labeling every reachable node with its nearest source
+/// (or [NearestSource::Tie]) and the distance to it.
+pub fn multi_source_bfs<N: Node, S: Into<N>>(
+    graph: &impl UnweightedGraph<Node = N>,
+    sources: impl IntoIterator<Item = S>,
+) -> HashMap<N, SourceDistance<N>> {
+    multi_source_bfs_impl(graph, sources, |graph, frontier, visited| {
+        frontier
+            .iter()
+            .flat_map(|(node, source)| {
+                graph
+                    .neighbors(node)
+                    .filter(|neighbor| !visited.contains_key(neighbor))
+                    .map(move |neighbor| (neighbor, source.clone()))
+            })
+            .collect()
+    })
+}
 
-            neighbors.extend(graph.neighbors(&node));
+/// Same as [multi_source_bfs], but expands each frontier in parallel
+#[cfg(feature = "rayon")]
+pub fn par_multi_source_bfs<N: Node + Send + Sync, S: Into<N>>(
+    graph: &(impl UnweightedGraph<Node = N> + Sync),
+    sources: impl IntoIterator<Item = S>,
+) -> HashMap<N, SourceDistance<N>> {
+    multi_source_bfs_impl(graph, sources, |graph, frontier, visited| {
+        frontier
+            .par_iter()
+            .flat_map_iter(|(node, source)| {
+                graph
+                    .neighbors(node)
+                    .filter(|neighbor| !visited.contains_key(neighbor))
+                    .map(move |neighbor| (neighbor, source.clone()))
+            })
+            .collect()
+    })
+}
+
+fn multi_source_bfs_impl<G: UnweightedGraph, S: Into<G::Node>>(
+    graph: &G,
+    sources: impl IntoIterator<Item = S>,
+    // Returns each unvisited neighbor of the frontier, paired with the source it was reached from
+    expand: impl Fn(
+        &G,
+        &HashMap<G::Node, NearestSource<G::Node>>,
+        &HashMap<G::Node, SourceDistance<G::Node>>,
+    ) -> Vec<(G::Node, NearestSource<G::Node>)>,
+) -> HashMap<G::Node, SourceDistance<G::Node>> {
+    let mut visited = HashMap::new();
+
+    let mut frontier = HashMap::new();
+    for source in sources {
+        let source = source.into();
+        frontier.insert(source.clone(), NearestSource::Unique(source));
+    }
+
+    let mut distance = 0;
+    while !frontier.is_empty() {
+        for (node, source) in &frontier {
+            visited.insert(node.clone(), SourceDistance { source: source.clone(), distance });
+        }
+
+        let mut next: HashMap<_, NearestSource<_>> = HashMap::new();
+        for (neighbor, source) in expand(graph, &frontier, &visited) {
+            let merged = match next.remove(&neighbor) {
+                Some(other) => other.merge(source),
+                None => source,
+            };
+            next.insert(neighbor, merged);
         }
-        next = neighbors.into_iter().filter(|n| !visited.contains_key(n)).collect();
+        frontier = next;
         distance += 1;
     }
+
+    visited
 }
 
 pub trait WeightedGraph {
@@ -123,6 +417,299 @@ pub fn dijkstra<T: Node, S: Into<T>>(
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Path<N> {
+    pub cost: Cost,
+    pub nodes: Vec<N>,
+}
+
+/// Like [dijkstra], but also returns the nodes along the shortest path (including start and end)
+pub fn dijkstra_path<N: Node>(
+    graph: &impl WeightedGraph<Node = N>,
+    start: impl Into<N>,
+    reached_target: impl Fn(&N) -> bool,
+) -> Option<Path<N>> {
+    dijkstra_path_excluding(graph, start.into(), &reached_target, &HashSet::new(), &HashSet::new())
+}
+
+fn dijkstra_path_excluding<N: Node>(
+    graph: &impl WeightedGraph<Node = N>,
+    start: N,
+    reached_target: &impl Fn(&N) -> bool,
+    removed_nodes: &HashSet<N>,
+    removed_edges: &HashSet<(N, N)>,
+) -> Option<Path<N>> {
+    let mut visited = HashSet::new();
+    let mut predecessors: HashMap<N, N> = HashMap::new();
+    let mut queue = PriorityQueue::new();
+    queue.push(start, Reverse(0));
+
+    while let Some((node, Reverse(current_cost))) = queue.pop() {
+        if reached_target(&node) {
+            let mut nodes = vec![node];
+            while let Some(predecessor) = predecessors.get(nodes.last().unwrap()) {
+                nodes.push(predecessor.clone());
+            }
+            nodes.reverse();
+            return Some(Path { cost: current_cost, nodes });
+        }
+        visited.insert(node.clone());
+
+        for (neighbor, cost) in graph.neighbors(&node) {
+            if visited.contains(&neighbor)
+                || removed_nodes.contains(&neighbor)
+                || removed_edges.contains(&(node.clone(), neighbor.clone()))
+            {
+                continue;
+            }
+            let new_cost = current_cost + cost;
+            match queue.get_priority(&neighbor) {
+                Some(Reverse(previous_cost)) if *previous_cost <= new_cost => {}
+                _ => {
+                    queue.push(neighbor.clone(), Reverse(new_cost));
+                    predecessors.insert(neighbor, node.clone());
+                }
+            }
+        }
+    }
+    None
+}
+
+fn edge_cost<N: Node>(graph: &impl WeightedGraph<Node = N>, from: &N, to: &N) -> Cost {
+    graph
+        .neighbors(from)
+        .filter(|(neighbor, _)| neighbor == to)
+        .map(|(_, cost)| cost)
+        .min()
+        .unwrap()
+}
+
+/// Iterator over all simple paths from a start node to a target, in order of increasing cost
+/// (Yen's algorithm). See [k_shortest_paths] and [paths_within].
+pub struct ShortestPaths<'a, G: WeightedGraph, F> {
+    graph: &'a G,
+    start: G::Node,
+    reached_target: F,
+    found: Vec<Path<G::Node>>,
+    candidates: BTreeSet<Path<G::Node>>,
+    done: bool,
+}
+
+pub fn shortest_paths<G: WeightedGraph, F: Fn(&G::Node) -> bool>(
+    graph: &G,
+    start: impl Into<G::Node>,
+    reached_target: F,
+) -> ShortestPaths<'_, G, F> {
+    ShortestPaths {
+        graph,
+        start: start.into(),
+        reached_target,
+        found: Vec::new(),
+        candidates: BTreeSet::new(),
+        done: false,
+    }
+}
+
+impl<G: WeightedGraph, F: Fn(&G::Node) -> bool> ShortestPaths<'_, G, F> {
+    fn add_spur_paths(&mut self) {
+        let previous = &self.found.last().unwrap().nodes;
+
+        let mut root_cost = 0;
+        for i in 0..previous.len() - 1 {
+            let root = &previous[..=i];
+            if i > 0 {
+                root_cost += edge_cost(self.graph, &previous[i - 1], &previous[i]);
+            }
+
+            let removed_edges = self
+                .found
+                .iter()
+                .filter(|path| path.nodes.len() > i + 1 && path.nodes[..=i] == *root)
+                .map(|path| (path.nodes[i].clone(), path.nodes[i + 1].clone()))
+                .collect();
+            let removed_nodes = root[..i].iter().cloned().collect();
+
+            if let Some(spur) = dijkstra_path_excluding(
+                self.graph,
+                previous[i].clone(),
+                &self.reached_target,
+                &removed_nodes,
+                &removed_edges,
+            ) {
+                let nodes = root[..i].iter().cloned().chain(spur.nodes).collect();
+                let candidate = Path { cost: root_cost + spur.cost, nodes };
+                if !self.found.contains(&candidate) {
+                    self.candidates.insert(candidate);
+                }
+            }
+        }
+    }
+}
+
+impl<G: WeightedGraph, F: Fn(&G::Node) -> bool> Iterator for ShortestPaths<'_, G, F> {
+    type Item = Path<G::Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let next = if self.found.is_empty() {
+            dijkstra_path(self.graph, self.start.clone(), &self.reached_target)
+        } else {
+            self.add_spur_paths();
+            self.candidates.pop_first()
+        };
+
+        match next {
+            Some(path) => {
+                self.found.push(path.clone());
+                Some(path)
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+/// The `k` cheapest simple paths, cheapest first. May return less than `k` paths.
+pub fn k_shortest_paths<N: Node>(
+    graph: &impl WeightedGraph<Node = N>,
+    start: impl Into<N>,
+    reached_target: impl Fn(&N) -> bool,
+    k: usize,
+) -> Vec<Path<N>> {
+    shortest_paths(graph, start, reached_target).take(k).collect()
+}
+
+/// All simple paths whose cost is at most `slack` more than the cost of the cheapest path
+pub fn paths_within<N: Node>(
+    graph: &impl WeightedGraph<Node = N>,
+    start: impl Into<N>,
+    reached_target: impl Fn(&N) -> bool,
+    slack: Cost,
+) -> Vec<Path<N>> {
+    let mut paths = shortest_paths(graph, start, reached_target).peekable();
+    let Some(max_cost) = paths.peek().map(|best| best.cost + slack) else {
+        return Vec::new();
+    };
+    paths.take_while(|path| path.cost <= max_cost).collect()
+}
+
+/// How [ida_star] avoids revisiting nodes. Checking more is slower per node but can prune a lot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleCheck {
+    /// Never terminates for cyclic graphs if the target is unreachable
+    None,
+    /// Never go back to the node we just came from. Still never terminates if the target is
+    /// unreachable and the graph has a cycle of length 3 or more.
+    Parent,
+    /// Never visit a node that is already on the current path
+    Path,
+}
+
+enum IdaStep {
+    Found(Cost),
+    /// Smallest f-cost that exceeded the threshold, None if the search space is exhausted
+    Exceeded(Option<Cost>),
+}
+
+/// Iterative deepening A*. Uses memory proportional to the length of the path only, at the cost
+/// of exploring nodes multiple times. `heuristic` must never overestimate the remaining cost.
+pub fn ida_star<N: Node>(
+    graph: &impl WeightedGraph<Node = N>,
+    start: impl Into<N>,
+    reached_target: impl Fn(&N) -> bool,
+    heuristic: impl Fn(&N) -> Cost,
+    cycle_check: CycleCheck,
+) -> Option<Path<N>> {
+    let start = start.into();
+    let mut threshold = heuristic(&start);
+    let mut on_path = HashSet::new();
+    if cycle_check == CycleCheck::Path {
+        on_path.insert(start.clone());
+    }
+    let mut nodes = vec![start];
+
+    loop {
+        let search = IdaSearch {
+            graph,
+            reached_target: &reached_target,
+            heuristic: &heuristic,
+            cycle_check,
+            threshold,
+        };
+        match search.search(&mut nodes, &mut on_path, 0) {
+            IdaStep::Found(cost) => return Some(Path { cost, nodes }),
+            IdaStep::Exceeded(None) => return None,
+            IdaStep::Exceeded(Some(next_threshold)) => threshold = next_threshold,
+        }
+    }
+}
+
+struct IdaSearch<'a, G, F, H> {
+    graph: &'a G,
+    reached_target: &'a F,
+    heuristic: &'a H,
+    cycle_check: CycleCheck,
+    threshold: Cost,
+}
+
+impl<G, F, H> IdaSearch<'_, G, F, H>
+where
+    G: WeightedGraph,
+    F: Fn(&G::Node) -> bool,
+    H: Fn(&G::Node) -> Cost,
+{
+    fn search(
+        &self,
+        nodes: &mut Vec<G::Node>,
+        on_path: &mut HashSet<G::Node>,
+        cost: Cost,
+    ) -> IdaStep {
+        let node = nodes.last().unwrap().clone();
+
+        let estimate = cost + (self.heuristic)(&node);
+        if estimate > self.threshold {
+            return IdaStep::Exceeded(Some(estimate));
+        }
+        if (self.reached_target)(&node) {
+            return IdaStep::Found(cost);
+        }
+
+        let parent = nodes.len().checked_sub(2).map(|i| nodes[i].clone());
+        let mut min_exceeded: Option<Cost> = None;
+        for (neighbor, edge_cost) in self.graph.neighbors(&node) {
+            let skip = match self.cycle_check {
+                CycleCheck::None => false,
+                CycleCheck::Parent => parent.as_ref() == Some(&neighbor),
+                CycleCheck::Path => on_path.contains(&neighbor),
+            };
+            if skip {
+                continue;
+            }
+
+            if self.cycle_check == CycleCheck::Path {
+                on_path.insert(neighbor.clone());
+            }
+            nodes.push(neighbor);
+
+            match self.search(nodes, on_path, cost + edge_cost) {
+                IdaStep::Found(cost) => return IdaStep::Found(cost),
+                IdaStep::Exceeded(exceeded) => {
+                    min_exceeded = min_exceeded.into_iter().chain(exceeded).min();
+                }
+            }
+
+            let neighbor = nodes.pop().unwrap();
+            on_path.remove(&neighbor);
+        }
+        IdaStep::Exceeded(min_exceeded)
+    }
+}
+
 pub enum NextState<I> {
     Terminal(usize),
     Next(I),
@@ -166,7 +753,10 @@ fn dfs_impl<T>(
 mod tests {
     use std::collections::HashMap;
 
-    use crate::{grid::Grid, math::Vec2D};
+    use crate::{
+        grid::Grid,
+        math::{Neighborhood, Vec2D},
+    };
 
     use super::*;
     use pretty_assertions::assert_eq;
@@ -205,20 +795,94 @@ mod tests {
     }
 
     #[test]
-    fn grid_diff() {
-        #[allow(non_local_definitions)]
-        impl WeightedGraph for Grid<usize> {
-            type Node = Vec2D;
+    fn yen() {
+        let mut edges = HashMap::new();
+        edges.insert("A", vec![("B", 1), ("D", 2)]);
+        edges.insert("B", vec![("C", 7)]);
+        edges.insert("C", vec![("F", 1)]);
+        edges.insert("D", vec![("C", 3), ("F", 10)]);
+        edges.insert("F", vec![]);
+        let graph = SimpleWeightedGraph { edges };
 
-            fn neighbors<'a, 'b: 'a>(
-                &'a self,
-                node: &'b Self::Node,
-            ) -> impl Iterator<Item = (Self::Node, Cost)> + 'a {
-                self.orthogonal_neighbors(node)
-                    .map(|neighbor| (neighbor, self[*node].abs_diff(self[neighbor])))
-            }
+        assert_eq!(
+            dijkstra_path(&graph, "A", |&node| node == "C"),
+            Some(Path { cost: 5, nodes: vec!["A", "D", "C"] })
+        );
+        assert_eq!(dijkstra_path(&graph, "A", |&node| node == "G"), None);
+
+        assert_eq!(
+            k_shortest_paths(&graph, "A", |&node| node == "F", 4),
+            vec![
+                Path { cost: 6, nodes: vec!["A", "D", "C", "F"] },
+                Path { cost: 9, nodes: vec!["A", "B", "C", "F"] },
+                Path { cost: 12, nodes: vec!["A", "D", "F"] },
+            ]
+        );
+        assert_eq!(
+            paths_within(&graph, "A", |&node| node == "F", 3),
+            vec![
+                Path { cost: 6, nodes: vec!["A", "D", "C", "F"] },
+                Path { cost: 9, nodes: vec!["A", "B", "C", "F"] },
+            ]
+        );
+        assert_eq!(paths_within(&graph, "A", |&node| node == "G", 3), vec![]);
+    }
+
+    #[test]
+    fn iterative_deepening() {
+        let mut edges = HashMap::new();
+        edges.insert("A", vec![("B", 1), ("D", 2)]);
+        edges.insert("B", vec![("A", 1), ("C", 7)]);
+        edges.insert("C", vec![("B", 7), ("D", 3), ("F", 1)]);
+        edges.insert("D", vec![("A", 2), ("C", 3), ("F", 10)]);
+        edges.insert("F", vec![("C", 1), ("D", 10)]);
+        edges.insert("G", vec![]);
+        let graph = SimpleWeightedGraph { edges };
+
+        for cycle_check in [CycleCheck::None, CycleCheck::Parent, CycleCheck::Path] {
+            assert_eq!(
+                ida_star(&graph, "A", |&node| node == "F", |_| 0, cycle_check),
+                Some(Path { cost: 6, nodes: vec!["A", "D", "C", "F"] })
+            );
+            assert_eq!(
+                ida_star(&graph, "A", |&node| node == "A", |_| 0, cycle_check),
+                Some(Path { cost: 0, nodes: vec!["A"] })
+            );
+        }
+        assert_eq!(ida_star(&graph, "A", |&node| node == "G", |_| 0, CycleCheck::Path), None);
+
+        // Without cycles of length >= 3 (a tree with edges in both directions), checking the
+        // parent is enough to terminate when the target is unreachable
+        let mut edges = HashMap::new();
+        edges.insert("A", vec![("B", 1), ("C", 2)]);
+        edges.insert("B", vec![("A", 1), ("D", 3)]);
+        edges.insert("C", vec![("A", 2)]);
+        edges.insert("D", vec![("B", 3)]);
+        edges.insert("G", vec![]);
+        let tree = SimpleWeightedGraph { edges };
+        for cycle_check in [CycleCheck::Parent, CycleCheck::Path] {
+            assert_eq!(ida_star(&tree, "A", |&node| node == "G", |_| 0, cycle_check), None);
+            assert_eq!(
+                ida_star(&tree, "C", |&node| node == "D", |_| 0, cycle_check),
+                Some(Path { cost: 6, nodes: vec!["C", "A", "B", "D"] })
+            );
         }
 
+        let heuristic = |node: &&str| match *node {
+            "A" => 5,
+            "B" => 6,
+            "C" => 1,
+            "D" => 4,
+            _ => 0,
+        };
+        assert_eq!(
+            ida_star(&graph, "A", |&node| node == "F", heuristic, CycleCheck::Parent),
+            Some(Path { cost: 6, nodes: vec!["A", "D", "C", "F"] })
+        );
+    }
+
+    #[test]
+    fn grid_diff() {
         let grid: Grid<usize> = vec![
             vec![0, 2, 9, 3, 1, 2],
             vec![1, 9, 1, 3, 3, 3],
@@ -226,26 +890,18 @@ mod tests {
             vec![9, 9, 9, 9, 9, 1],
         ]
         .into();
+        let graph = grid.graph(|_| true).cost(|from, to| from.abs_diff(*to));
 
-        assert_eq!(dijkstra(&grid, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(5, 3)), Some(11));
-        assert_eq!(dijkstra(&grid, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(-1, -1)), None);
-        assert_eq!(dijkstra(&grid, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(0, 0)), Some(0));
+        assert_eq!(
+            dijkstra(&graph, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(5, 3)),
+            Some(11)
+        );
+        assert_eq!(dijkstra(&graph, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(-1, -1)), None);
+        assert_eq!(dijkstra(&graph, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(0, 0)), Some(0));
     }
 
     #[test]
     fn grid_bfs() {
-        #[allow(non_local_definitions)]
-        impl UnweightedGraph for Grid<char> {
-            type Node = Vec2D;
-
-            fn neighbors<'a, 'b: 'a>(
-                &'a self,
-                node: &'b Vec2D,
-            ) -> impl Iterator<Item = Vec2D> + 'a {
-                self.all_neighbors(node).filter(move |pos| self[*pos] != '#')
-            }
-        }
-
         let grid: Grid<char> = vec![
             vec!['.', '#', '#', '.', '.', '.', '.'],
             vec!['.', '.', '#', '.', '#', '#', '.'],
@@ -254,6 +910,8 @@ mod tests {
         ]
         .into();
 
+        let grid = grid.graph(|&c| c != '#').neighborhood(Neighborhood::All);
+
         assert_eq!(bfs(&grid, (0, 0), (6, 3)).distance, Some(11));
         assert_eq!(bfs(&grid, (0, 0), (2, 2)).distance, None);
         assert_eq!(bfs(&grid, (0, 0), (0, 0)).distance, Some(0));
@@ -278,6 +936,132 @@ mod tests {
         );
     }
 
+    struct Plane;
+
+    impl UnweightedGraph for Plane {
+        type Node = Vec2D;
+
+        fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
+            node.orthogonal_neighbors()
+        }
+    }
+
+    #[test]
+    fn layers() {
+        let mut layers = bfs_layers(&Plane, (0, 0)).max_depth(2);
+        assert_eq!(layers.next(), Some((0, HashSet::from([Vec2D::new(0, 0)]))));
+        assert_eq!(
+            layers.next(),
+            Some((
+                1,
+                HashSet::from([
+                    Vec2D::new(1, 0),
+                    Vec2D::new(-1, 0),
+                    Vec2D::new(0, 1),
+                    Vec2D::new(0, -1)
+                ])
+            ))
+        );
+        assert_eq!(layers.next().map(|(distance, layer)| (distance, layer.len())), Some((2, 8)));
+        assert_eq!(layers.next(), None);
+        assert_eq!(layers.visited().len(), 13);
+
+        assert_eq!(reachable_in_exactly(&Plane, (0, 0), 0), HashSet::from([Vec2D::new(0, 0)]));
+        assert_eq!(reachable_in_exactly(&Plane, (0, 0), 6).len(), 49);
+        assert_eq!(reachable_in_exactly(&Plane, (0, 0), 7).len(), 64);
+        assert!(!reachable_in_exactly(&Plane, (0, 0), 7).contains(&Vec2D::new(0, 0)));
+
+        assert_eq!(reachable_in_exactly(&Square(1), (0, 0), 0), HashSet::from([Vec2D::new(0, 0)]));
+        assert_eq!(reachable_in_exactly(&Square(1), (0, 0), 2), HashSet::new());
+    }
+
+    /// Counts how often the neighbors of a node are requested
+    struct CountingPlane(std::cell::Cell<usize>);
+
+    impl UnweightedGraph for CountingPlane {
+        type Node = Vec2D;
+
+        fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
+            self.0.set(self.0.get() + 1);
+            node.orthogonal_neighbors()
+        }
+    }
+
+    #[test]
+    fn bfs_stops_at_target() {
+        let plane = CountingPlane(Default::default());
+        assert_eq!(bfs(&plane, (0, 0), (0, 0)).distance, Some(0));
+        assert_eq!(plane.0.get(), 0);
+
+        assert_eq!(bfs(&plane, (0, 0), (10, 0)).distance, Some(10));
+        // Only the nodes closer than the target are expanded
+        assert_eq!(plane.0.get(), 181);
+
+        plane.0.set(0);
+        assert_eq!(bfs_layers(&plane, (0, 0)).max_depth(3).count(), 4);
+        assert_eq!(plane.0.get(), 1 + 4 + 8);
+    }
+
+    /// Grid of size x size without any walls
+    struct Square(i64);
+
+    impl UnweightedGraph for Square {
+        type Node = Vec2D;
+
+        fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
+            node.orthogonal_neighbors().filter(|n| n.inside_box((0, 0), (self.0 - 1, self.0 - 1)))
+        }
+    }
+
+    #[test]
+    fn multi_source() {
+        let labels = multi_source_bfs(&Square(5), [(0, 0), (4, 0)]);
+        assert_eq!(labels.len(), 25);
+
+        let a = NearestSource::Unique(Vec2D::new(0, 0));
+        let b = NearestSource::Unique(Vec2D::new(4, 0));
+        assert_eq!(labels[&Vec2D::new(0, 0)], SourceDistance { source: a, distance: 0 });
+        assert_eq!(labels[&Vec2D::new(1, 0)], SourceDistance { source: a, distance: 1 });
+        assert_eq!(labels[&Vec2D::new(3, 3)], SourceDistance { source: b, distance: 4 });
+        assert_eq!(
+            labels[&Vec2D::new(2, 0)],
+            SourceDistance { source: NearestSource::Tie, distance: 2 }
+        );
+        assert_eq!(
+            labels[&Vec2D::new(2, 4)],
+            SourceDistance { source: NearestSource::Tie, distance: 6 }
+        );
+        assert_eq!(labels.values().filter(|label| label.source == a).count(), 10);
+        assert_eq!(labels.values().filter(|label| label.source.unique().is_none()).count(), 5);
+
+        let mut layers = bfs_layers_from(&Square(5), [(0, 0), (4, 0)]);
+        assert_eq!(layers.next().map(|(_, layer)| layer.len()), Some(2));
+        assert_eq!(layers.next().map(|(_, layer)| layer.len()), Some(4));
+
+        let starts = [(0, 0), (4, 0)];
+        assert_eq!(bfs_from(&Square(5), starts, (3, 3)).distance, Some(4));
+        assert_eq!(bfs_predicate_from(&Square(5), starts, |pos| pos.y == 4).distance, Some(4));
+        assert_eq!(bfs_predicate_from(&Square(5), starts, |pos| pos.y == 5).distance, None);
+        let distances = floodfill_from(&Square(5), starts);
+        assert!(labels.iter().all(|(node, label)| distances[node] == label.distance));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let graph = Square(30);
+        assert_eq!(par_bfs(&graph, (0, 0), (29, 17)), bfs(&graph, (0, 0), (29, 17)));
+        assert_eq!(par_bfs(&graph, (0, 0), (30, 30)), bfs(&graph, (0, 0), (30, 30)));
+        assert_eq!(
+            par_bfs_predicate(&graph, (3, 4), |pos| pos.x == 20),
+            bfs_predicate(&graph, (3, 4), |pos| pos.x == 20)
+        );
+        assert_eq!(par_floodfill(&graph, (5, 5)), floodfill(&graph, (5, 5)));
+
+        let sources = [(0, 0), (29, 0), (13, 21), (7, 7)];
+        assert_eq!(par_multi_source_bfs(&graph, sources), multi_source_bfs(&graph, sources));
+    }
+
     #[test]
     fn test_dfs() {
         #[derive(Debug, Clone, Eq, PartialEq, Hash)]
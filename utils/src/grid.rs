@@ -2,11 +2,34 @@ use std::ops::{Index, IndexMut};
 
 use itertools::Itertools;
 
-use crate::math::Vec2D;
-
+use crate::math::{Neighborhood, Vec2D};
+
+mod graph;
+pub use graph::*;
+mod pattern;
+pub use pattern::*;
+mod pose_graph;
+pub use pose_graph::*;
+mod rays;
+mod reflection;
+pub use reflection::*;
+mod regions;
+pub use regions::*;
+mod reshape;
+pub use reshape::*;
+mod tiled;
+mod tilt;
+pub use tiled::*;
+mod transform;
+mod view;
+pub use view::*;
+
+/// Dense 2D grid, stored row-major in a single contiguous `Vec`
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Grid<T> {
-    data: Vec<Vec<T>>,
+    data: Vec<T>,
+    num_rows: usize,
+    num_cols: usize,
 }
 
 impl<T, InnerIter> FromIterator<InnerIter> for Grid<T>
@@ -22,11 +45,21 @@ impl<T, InnerIter: IntoIterator<Item = T>, Iter: IntoIterator<Item = InnerIter>>
     for Grid<T>
 {
     fn from(iter: Iter) -> Self {
-        let grid = Grid {
-            data: iter.into_iter().map(|iter| iter.into_iter().collect_vec()).collect_vec(),
-        };
-        assert!(grid.data.iter().all(|row| row.len() == grid.data[0].len()));
-        grid
+        let mut data = Vec::new();
+        let mut num_cols = None;
+        let mut num_rows = 0;
+        for row in iter {
+            num_rows += 1;
+            let len_before = data.len();
+            data.extend(row);
+            let row_len = data.len() - len_before;
+            assert_eq!(
+                *num_cols.get_or_insert(row_len),
+                row_len,
+                "All rows must have equal length"
+            );
+        }
+        Grid { data, num_rows, num_cols: num_cols.unwrap_or(0) }
     }
 }
 
@@ -68,40 +101,48 @@ where
 
 impl<T> Grid<T> {
     pub fn new(data: Vec<Vec<T>>) -> Self {
-        assert!(data.iter().all(|row| row.len() == data[0].len()));
-        Grid { data }
+        data.into()
     }
 
-    pub fn inner(&self) -> &Vec<Vec<T>> {
+    /// Create a grid from its cells in row-major order
+    pub fn from_flat(data: Vec<T>, num_cols: usize) -> Self {
+        assert!(num_cols > 0 && data.len().is_multiple_of(num_cols));
+        Grid { num_rows: data.len() / num_cols, data, num_cols }
+    }
+
+    /// All cells in row-major order
+    pub fn inner(&self) -> &[T] {
         &self.data
     }
 
-    pub fn inner_mut(&mut self) -> &mut Vec<Vec<T>> {
+    pub fn inner_mut(&mut self) -> &mut [T] {
         &mut self.data
     }
 
-    pub fn get_wrapping(&self, pos: impl Into<Vec2D>) -> &T {
-        let mut pos = pos.into();
-
-        while pos.x < 0 {
-            pos.x += self.num_cols() as i64;
-        }
+    pub fn into_inner(self) -> Vec<T> {
+        self.data
+    }
 
-        while pos.y < 0 {
-            pos.y += self.num_rows() as i64;
-        }
+    fn index_of(&self, pos: &Vec2D) -> usize {
+        pos.y as usize * self.num_cols + pos.x as usize
+    }
 
-        &self[(pos.x % self.num_cols() as i64, pos.y % self.num_cols() as i64)]
+    pub fn get_wrapping(&self, pos: impl Into<Vec2D>) -> &T {
+        self.tiled().get(pos)
     }
 
     pub fn get(&self, pos: impl Into<Vec2D>) -> Option<&T> {
         let pos = pos.into();
-        self.contains(&pos).then(|| &self.data[pos.y as usize][pos.x as usize])
+        self.contains(&pos).then(|| &self.data[self.index_of(&pos)])
     }
 
     pub fn get_mut(&mut self, pos: impl Into<Vec2D>) -> Option<&mut T> {
         let pos = pos.into();
-        self.contains(&pos).then(|| &mut self.data[pos.y as usize][pos.x as usize])
+        if !self.contains(&pos) {
+            return None;
+        }
+        let index = self.index_of(&pos);
+        Some(&mut self.data[index])
     }
 
     pub fn contains(&self, pos: &Vec2D) -> bool {
@@ -109,7 +150,17 @@ impl<T> Grid<T> {
     }
 
     pub fn num_rows(&self) -> usize {
-        self.data.len()
+        self.num_rows
+    }
+
+    pub fn row_slice(&self, row: impl UnwrapIntoUsize) -> &[T] {
+        let start = row.unwrap_usize() * self.num_cols;
+        &self.data[start..start + self.num_cols]
+    }
+
+    pub fn row_slice_mut(&mut self, row: impl UnwrapIntoUsize) -> &mut [T] {
+        let start = row.unwrap_usize() * self.num_cols;
+        &mut self.data[start..start + self.num_cols]
     }
 
     pub fn row(
@@ -117,7 +168,7 @@ impl<T> Grid<T> {
         row: impl UnwrapIntoUsize,
     ) -> impl DoubleEndedIterator<Item = (Vec2D, &T)> + '_ {
         let row = row.unwrap_usize();
-        self.data[row].iter().enumerate().map(move |(col, item)| ((col, row).into(), item))
+        self.row_slice(row).iter().enumerate().map(move |(col, item)| ((col, row).into(), item))
     }
 
     pub fn row_values(&self, row: impl UnwrapIntoUsize) -> impl Iterator<Item = &T> + '_ {
@@ -125,11 +176,11 @@ impl<T> Grid<T> {
     }
 
     pub fn rotate_row_left(&mut self, row: usize, mid: usize) {
-        self.data[row].rotate_left(mid);
+        self.row_slice_mut(row).rotate_left(mid);
     }
 
     pub fn rotate_row_right(&mut self, row: usize, mid: usize) {
-        self.data[row].rotate_right(mid);
+        self.row_slice_mut(row).rotate_right(mid);
     }
 
     pub fn rows(
@@ -139,7 +190,7 @@ impl<T> Grid<T> {
     }
 
     pub fn num_cols(&self) -> usize {
-        self.data[0].len()
+        self.num_cols
     }
 
     pub fn col(&self, col: impl UnwrapIntoUsize) -> ColIter<'_, T> {
@@ -167,15 +218,26 @@ impl<T> Grid<T> {
     }
 
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Vec2D, &T)> + '_ {
-        self.coordinates_row_major().map(move |pos| (pos, &self[pos]))
+        self.data.chunks_exact(self.num_cols.max(1)).enumerate().flat_map(|(y, row)| {
+            row.iter().enumerate().map(move |(x, val)| (Vec2D::new(x as i64, y as i64), val))
+        })
     }
 
     pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (Vec2D, &mut T)> + '_ {
-        self.data.iter_mut().enumerate().flat_map(|(y, col)| {
-            col.iter_mut().enumerate().map(move |(x, val)| (Vec2D::from((x, y)), val))
+        self.data.chunks_exact_mut(self.num_cols.max(1)).enumerate().flat_map(|(y, row)| {
+            row.iter_mut().enumerate().map(move |(x, val)| (Vec2D::new(x as i64, y as i64), val))
         })
     }
 
+    /// New grid of the same shape with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            num_rows: self.num_rows,
+            num_cols: self.num_cols,
+        }
+    }
+
     pub fn orthogonal_neighbors<'a, 'b: 'a>(
         &'a self,
         pos: &'b Vec2D,
@@ -212,6 +274,22 @@ impl<T> Grid<T> {
         pos.all_neighbors().filter(move |neighbor| self.contains(neighbor))
     }
 
+    pub fn neighbors<'a, 'b: 'a>(
+        &'a self,
+        pos: &'b Vec2D,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = Vec2D> + 'a {
+        pos.neighbors(neighborhood).filter(move |neighbor| self.contains(neighbor))
+    }
+
+    pub fn neighbor_values<'a, 'b: 'a>(
+        &'a self,
+        pos: &'b Vec2D,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        pos.neighbors(neighborhood).filter_map(|neighbor| self.get(neighbor))
+    }
+
     pub fn all_neighbor_values<'a, 'b: 'a>(
         &'a self,
         pos: &'b Vec2D,
@@ -236,7 +314,7 @@ where
     T: Clone,
 {
     pub fn with_value(val: T, num_rows: usize, num_cols: usize) -> Self {
-        Grid { data: vec![vec![val; num_cols]; num_rows] }
+        Grid { data: vec![val; num_rows * num_cols], num_rows, num_cols }
     }
 
     pub fn pad_edges(self, with: T) -> Self {
@@ -263,7 +341,7 @@ where
         }
 
         for (row, item) in new_col.into_iter().enumerate() {
-            self.data[row][col] = item;
+            self.data[row * self.num_cols + col] = item;
         }
     }
 
@@ -286,6 +364,7 @@ pub struct ColIter<'a, T> {
 
 impl<'a, T> ColIter<'a, T> {
     fn new(grid: &'a Grid<T>, col: usize) -> Self {
+        assert!(col < grid.num_cols(), "column {col} out of bounds");
         ColIter { grid, row: 0, row_back: grid.num_rows(), col }
     }
 
@@ -302,7 +381,7 @@ impl<'a, T> Iterator for ColIter<'a, T> {
             return None;
         }
         let pos = (self.col, self.row).into();
-        let item = (pos, &self.grid[pos]);
+        let item = (pos, &self.grid.data[self.row * self.grid.num_cols + self.col]);
         self.row += 1;
         Some(item)
     }
@@ -320,7 +399,7 @@ impl<T> DoubleEndedIterator for ColIter<'_, T> {
         }
         self.row_back -= 1;
         let pos = (self.col, self.row_back).into();
-        let item = (pos, &self.grid[pos]);
+        let item = (pos, &self.grid.data[self.row_back * self.grid.num_cols + self.col]);
         Some(item)
     }
 }
@@ -399,27 +478,34 @@ mod tests {
         );
     }
 
+    #[test]
+    #[should_panic]
+    fn col_out_of_bounds() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6], [7, 8, 9]].into();
+        grid.col(3).next();
+    }
+
     #[test]
     fn rotate_rows_and_cols() {
         let mut grid: Grid<_> = [[1, 2, 3], [4, 5, 6], [7, 8, 9]].into();
 
         grid.rotate_row_left(0, 1);
-        assert_eq!(grid.data, [[2, 3, 1], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[2, 3, 1], [4, 5, 6], [7, 8, 9]]));
 
         grid.rotate_row_right(0, 1);
-        assert_eq!(grid.data, [[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]));
 
         grid.rotate_row_left(1, 2);
-        assert_eq!(grid.data, [[1, 2, 3], [6, 4, 5], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [6, 4, 5], [7, 8, 9]]));
 
         grid.rotate_row_right(1, 2);
-        assert_eq!(grid.data, [[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]));
 
         grid.rotate_col_up(0, 1);
-        assert_eq!(grid.data, [[4, 2, 3], [7, 5, 6], [1, 8, 9]]);
+        assert_eq!(grid, Grid::from([[4, 2, 3], [7, 5, 6], [1, 8, 9]]));
 
         grid.rotate_col_down(0, 2);
-        assert_eq!(grid.data, [[7, 2, 3], [1, 5, 6], [4, 8, 9]]);
+        assert_eq!(grid, Grid::from([[7, 2, 3], [1, 5, 6], [4, 8, 9]]));
     }
 
     #[test]
@@ -427,16 +513,32 @@ mod tests {
         let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
         let padded = grid.pad_edges(0);
         assert_eq!(
-            padded.data,
-            vec![
-                vec![0, 0, 0, 0, 0],
-                vec![0, 1, 2, 3, 0],
-                vec![0, 4, 5, 6, 0],
-                vec![0, 0, 0, 0, 0],
-            ]
+            padded,
+            Grid::from([[0, 0, 0, 0, 0], [0, 1, 2, 3, 0], [0, 4, 5, 6, 0], [0, 0, 0, 0, 0],])
         );
     }
 
+    #[test]
+    fn flat_storage() {
+        let mut grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        assert_eq!(grid.inner(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.row_slice(1), &[4, 5, 6]);
+        assert_eq!(grid, Grid::from_flat(vec![1, 2, 3, 4, 5, 6], 3));
+        assert_eq!(grid, Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]));
+
+        grid.inner_mut()[4] = 0;
+        assert_eq!(grid[(1, 1)], 0);
+        assert_eq!(grid.get_mut((-1, 1)), None);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((-1, 1)), None);
+        assert_eq!(grid.map(|x| x * 2), Grid::from([[2, 4, 6], [8, 0, 12]]));
+        assert_eq!(grid.into_inner(), vec![1, 2, 3, 4, 0, 6]);
+
+        let empty: Grid<i32> = Grid::from(Vec::<Vec<i32>>::new());
+        assert_eq!(empty.num_rows(), 0);
+        assert_eq!(empty.iter().count(), 0);
+    }
+
     #[test]
     fn get_wrapping() {
         let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
@@ -446,6 +548,8 @@ mod tests {
         assert_eq!(grid.get_wrapping((-6, -2)), &1);
         assert_eq!(grid.get_wrapping((-1, 1)), &6);
         assert_eq!(grid.get_wrapping((-1, -1)), &6);
+        assert_eq!(grid.get_wrapping((0, 2)), &1);
+        assert_eq!(grid.get_wrapping((1, 3)), &5);
     }
 
     #[test]
@@ -0,0 +1,105 @@
+use crate::{
+    graphs::{Cost, UnweightedGraph, WeightedGraph},
+    math::{Neighborhood, Vec2D},
+};
+
+use super::Grid;
+
+/// Graph whose nodes are the positions of a [Grid], created by [Grid::graph].
+///
+/// Cells can only be entered if `passable` returns true for them. Moving between neighbors costs
+/// `cost(from, to)` when used as a [WeightedGraph] (1 by default).
+pub struct GridGraph<'a, T, P, C = fn(&T, &T) -> Cost> {
+    grid: &'a Grid<T>,
+    passable: P,
+    neighborhood: Neighborhood,
+    cost: C,
+}
+
+impl<T> Grid<T> {
+    /// Graph with orthogonal neighbors, see [GridGraph::neighborhood] and [GridGraph::cost] to
+    /// customize it
+    pub fn graph<P: Fn(&T) -> bool>(&self, passable: P) -> GridGraph<'_, T, P> {
+        GridGraph { grid: self, passable, neighborhood: Neighborhood::Orthogonal, cost: |_, _| 1 }
+    }
+}
+
+impl<'a, T, P, C> GridGraph<'a, T, P, C>
+where
+    P: Fn(&T) -> bool,
+{
+    pub fn neighborhood(self, neighborhood: Neighborhood) -> Self {
+        GridGraph { neighborhood, ..self }
+    }
+
+    pub fn cost<F: Fn(&T, &T) -> Cost>(self, cost: F) -> GridGraph<'a, T, P, F> {
+        let GridGraph { grid, passable, neighborhood, .. } = self;
+        GridGraph { grid, passable, neighborhood, cost }
+    }
+
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    fn passable_neighbors<'b>(&'b self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'b {
+        self.grid
+            .neighbors(node, self.neighborhood)
+            .filter(|neighbor| (self.passable)(&self.grid[*neighbor]))
+    }
+}
+
+impl<T, P, C> UnweightedGraph for GridGraph<'_, T, P, C>
+where
+    P: Fn(&T) -> bool,
+{
+    type Node = Vec2D;
+
+    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
+        self.passable_neighbors(node)
+    }
+}
+
+impl<T, P, C> WeightedGraph for GridGraph<'_, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn(&T, &T) -> Cost,
+{
+    type Node = Vec2D;
+
+    fn neighbors<'a, 'b: 'a>(
+        &'a self,
+        node: &'b Vec2D,
+    ) -> impl Iterator<Item = (Vec2D, Cost)> + 'a {
+        self.passable_neighbors(node)
+            .map(|neighbor| (neighbor, (self.cost)(&self.grid[*node], &self.grid[neighbor])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphs::{bfs, dijkstra};
+
+    use super::*;
+
+    #[test]
+    fn rules() {
+        let grid: Grid<char> = [['.', '#', '.'], ['.', '#', '.'], ['.', '.', '.']].into();
+
+        let graph = grid.graph(|&c| c != '#');
+        assert_eq!(bfs(&graph, (0, 0), (2, 0)).distance, Some(6));
+        assert_eq!(dijkstra(&graph, [(0, 0)], |&pos| pos == (2, 0)), Some(6));
+
+        let graph = graph.neighborhood(Neighborhood::All);
+        assert_eq!(bfs(&graph, (0, 0), (2, 0)).distance, Some(4));
+
+        let walls_only = grid.graph(|&c| c == '#');
+        assert_eq!(bfs(&walls_only, (1, 0), (1, 1)).distance, Some(1));
+        assert_eq!(bfs(&walls_only, (1, 0), (1, 2)).distance, None);
+
+        let swamp: Grid<char> = [['.', '~', '.'], ['.', '.', '.']].into();
+        let graph = swamp.graph(|_| true);
+        assert_eq!(dijkstra(&graph, [(0, 0)], |&pos| pos == (2, 0)), Some(2));
+        let graph = graph.cost(|_, &to| if to == '~' { 5 } else { 1 });
+        assert_eq!(dijkstra(&graph, [(0, 0)], |&pos| pos == (2, 0)), Some(4));
+    }
+}
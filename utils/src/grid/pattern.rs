@@ -0,0 +1,146 @@
+use itertools::Itertools;
+
+use crate::math::Vec2D;
+
+use super::Grid;
+
+/// Build a pattern for [Grid::find_pattern] from lines of characters, where `wildcard` matches
+/// any cell. Rows shorter than the longest one are padded with wildcards.
+pub fn char_pattern(s: &str, wildcard: char) -> Grid<Option<char>> {
+    let num_cols = s.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    s.lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| (c != wildcard).then_some(c))
+                .pad_using(num_cols, |_| None)
+                .collect_vec()
+        })
+        .collect()
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    fn matches_at(&self, top_left: Vec2D, pattern: &Grid<Option<T>>) -> bool {
+        pattern.iter().all(|(pos, expected)| match expected {
+            Some(expected) => self.get(top_left + pos) == Some(expected),
+            None => true,
+        })
+    }
+
+    /// Top left corners of all places where `pattern` matches the grid, in row-major order.
+    /// `None` cells in the pattern are wildcards. An empty pattern matches nowhere.
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>) -> Vec<Vec2D> {
+        if pattern.num_rows() == 0 || pattern.num_cols() == 0 {
+            return Vec::new();
+        }
+        let rows = (self.num_rows() + 1).saturating_sub(pattern.num_rows());
+        let cols = (self.num_cols() + 1).saturating_sub(pattern.num_cols());
+        (0..rows)
+            .cartesian_product(0..cols)
+            .map(|(y, x)| Vec2D::from((x, y)))
+            .filter(|&top_left| self.matches_at(top_left, pattern))
+            .collect()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq + Clone,
+{
+    /// Like [Grid::find_pattern], but also tries all rotations and flips of the pattern.
+    /// Returns each match together with the orientation of the pattern that matched. Orientations
+    /// that are identical due to symmetry of the pattern are only tried once.
+    pub fn find_pattern_any_orientation(
+        &self,
+        pattern: &Grid<Option<T>>,
+    ) -> Vec<(Vec2D, Grid<Option<T>>)> {
+        let mut orientations: Vec<Grid<Option<T>>> = Vec::new();
+        for orientation in pattern.symmetries() {
+            if !orientations.contains(&orientation) {
+                orientations.push(orientation);
+            }
+        }
+
+        orientations
+            .into_iter()
+            .flat_map(|orientation| {
+                self.find_pattern(&orientation)
+                    .into_iter()
+                    .map(move |pos| (pos, orientation.clone()))
+                    .collect_vec()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    #[test]
+    fn find_pattern() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 1, 2], [7, 4, 1]].into();
+        let pattern: Grid<_> = [[Some(2), Some(3)], [Some(1), None]].into();
+        assert_eq!(grid.find_pattern(&pattern), vec![Vec2D::new(1, 0)]);
+
+        let pattern: Grid<_> = [[Some(1), None], [None, Some(1)]].into();
+        assert_eq!(grid.find_pattern(&pattern), vec![Vec2D::new(0, 0), Vec2D::new(1, 1)]);
+
+        let too_large: Grid<Option<i32>> = Grid::with_value(None, 4, 1);
+        assert!(grid.find_pattern(&too_large).is_empty());
+
+        assert!(grid.find_pattern(&Grid::<Option<i32>>::with_value(None, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn word_search() {
+        let grid = Input::from(
+            "
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX",
+        )
+        .char_grid();
+
+        let straight = char_pattern("XMAS", '.');
+        let diagonal = char_pattern("X...\n.M..\n..A.\n...S", '.');
+        assert_eq!(
+            grid.find_pattern_any_orientation(&straight).len()
+                + grid.find_pattern_any_orientation(&diagonal).len(),
+            18
+        );
+
+        let cross = char_pattern("M.S\n.A.\nM.S", '.');
+        assert_eq!(grid.find_pattern_any_orientation(&cross).len(), 9);
+    }
+
+    #[test]
+    fn sea_monster() {
+        let monster =
+            char_pattern("                  # \n#    ##    ##    ###\n #  #  #  #  #  #", ' ');
+        assert_eq!((monster.num_rows(), monster.num_cols()), (3, 20));
+        assert_eq!(monster.iter().filter(|(_, c)| c.is_some()).count(), 15);
+
+        let mut grid = Grid::with_value('.', 5, 22);
+        for (pos, c) in monster.iter() {
+            if c.is_some() {
+                grid[pos + (1, 1)] = '#';
+            }
+        }
+        let grid = grid.rotated_left();
+        let matches = grid.find_pattern_any_orientation(&monster);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].1, monster.rotated_left());
+    }
+}
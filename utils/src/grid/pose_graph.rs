@@ -0,0 +1,179 @@
+use crate::{
+    graphs::{Cost, WeightedGraph},
+    math::{Direction, Pose, Vec2D},
+};
+
+use super::Grid;
+
+/// Node of a [PoseGraph]: where we are, where we're facing, and how many steps we've taken
+/// straight ahead since the last turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PoseState {
+    pub pose: Pose,
+    pub straight: usize,
+}
+
+impl PoseState {
+    pub fn new(pos: impl Into<Vec2D>, dir: Direction) -> Self {
+        PoseState { pose: Pose::new(pos, dir), straight: 0 }
+    }
+}
+
+impl From<Pose> for PoseState {
+    fn from(pose: Pose) -> Self {
+        PoseState { pose, straight: 0 }
+    }
+}
+
+/// Weighted graph for walking around a [Grid] while keeping track of the heading, created by
+/// [Grid::pose_graph].
+///
+/// From each state we can either step forward into a passable cell, or turn left/right and then
+/// step forward. Each step costs `step_cost` of the entered cell (1 by default) and each turn
+/// additionally costs `turn_cost` (0 by default). Turning around is not possible.
+/// With [PoseGraph::straight_run], we must take at least `min` straight steps before turning and
+/// may take at most `max` straight steps in a row.
+pub struct PoseGraph<'a, T, P, C = fn(&T) -> Cost> {
+    grid: &'a Grid<T>,
+    passable: P,
+    step_cost: C,
+    turn_cost: Cost,
+    min_straight: usize,
+    max_straight: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn pose_graph<P: Fn(&T) -> bool>(&self, passable: P) -> PoseGraph<'_, T, P> {
+        PoseGraph {
+            grid: self,
+            passable,
+            step_cost: |_| 1,
+            turn_cost: 0,
+            min_straight: 0,
+            max_straight: usize::MAX,
+        }
+    }
+}
+
+impl<'a, T, P, C> PoseGraph<'a, T, P, C> {
+    pub fn step_cost<F: Fn(&T) -> Cost>(self, step_cost: F) -> PoseGraph<'a, T, P, F> {
+        let PoseGraph { grid, passable, turn_cost, min_straight, max_straight, .. } = self;
+        PoseGraph { grid, passable, step_cost, turn_cost, min_straight, max_straight }
+    }
+
+    pub fn turn_cost(self, turn_cost: Cost) -> Self {
+        PoseGraph { turn_cost, ..self }
+    }
+
+    pub fn straight_run(self, min: usize, max: usize) -> Self {
+        PoseGraph { min_straight: min, max_straight: max, ..self }
+    }
+
+    /// Whether we're allowed to stop in `state`, i.e. we've walked straight for long enough
+    pub fn can_stop(&self, state: &PoseState) -> bool {
+        state.straight >= self.min_straight
+    }
+}
+
+impl<T, P, C> WeightedGraph for PoseGraph<'_, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn(&T) -> Cost,
+{
+    type Node = PoseState;
+
+    fn neighbors<'a, 'b: 'a>(
+        &'a self,
+        node: &'b PoseState,
+    ) -> impl Iterator<Item = (PoseState, Cost)> + 'a {
+        let mut moves = Vec::with_capacity(3);
+        if node.straight < self.max_straight {
+            moves.push((node.pose, node.straight + 1, 0));
+        }
+        if self.can_stop(node) {
+            for turned in [node.pose.turned_left(), node.pose.turned_right()] {
+                moves.push((turned, 1, self.turn_cost));
+            }
+        }
+
+        let neighbors = moves.into_iter().filter_map(move |(pose, straight, turn_cost)| {
+            let pose = pose.advanced();
+            let cell = self.grid.get(pose.pos).filter(|cell| (self.passable)(cell))?;
+            Some((PoseState { pose, straight }, turn_cost + (self.step_cost)(cell)))
+        });
+
+        neighbors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{graphs::dijkstra, Input};
+
+    use super::*;
+
+    #[test]
+    fn crucible() {
+        let grid = Input::from(
+            "
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533",
+        )
+        .number_grid();
+        let end = Vec2D::from((grid.num_cols() - 1, grid.num_rows() - 1));
+        let start =
+            [PoseState::new((0, 0), Direction::Right), PoseState::new((0, 0), Direction::Down)];
+
+        let graph = grid.pose_graph(|_| true).step_cost(|&cost| cost).straight_run(0, 3);
+        assert_eq!(dijkstra(&graph, start, |state| state.pose.pos == end), Some(102));
+
+        let graph = graph.straight_run(4, 10);
+        assert_eq!(
+            dijkstra(&graph, start, |state| state.pose.pos == end && graph.can_stop(state)),
+            Some(94)
+        );
+    }
+
+    #[test]
+    fn reindeer_maze() {
+        let grid = Input::from(
+            "
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############",
+        )
+        .char_grid();
+        let start = grid.find_position(&'S').unwrap();
+        let end = grid.find_position(&'E').unwrap();
+
+        let graph = grid.pose_graph(|&c| c != '#').turn_cost(1000);
+        assert_eq!(
+            dijkstra(&graph, [PoseState::new(start, Direction::Right)], |state| state.pose.pos
+                == end),
+            Some(7036)
+        );
+    }
+}
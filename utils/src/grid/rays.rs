@@ -0,0 +1,121 @@
+use crate::math::Vec2D;
+
+use super::Grid;
+
+impl<T> Grid<T> {
+    /// All cells at `start + step`, `start + 2 * step`, ... until leaving the grid
+    pub fn ray(
+        &self,
+        start: impl Into<Vec2D>,
+        step: impl Into<Vec2D>,
+    ) -> impl Iterator<Item = (Vec2D, &T)> + '_ {
+        let (start, step) = (start.into(), step.into());
+        assert!(!step.is_zero());
+        std::iter::successors(Some(start + step), move |&pos| Some(pos + step))
+            .map_while(|pos| self.get(pos).map(|val| (pos, val)))
+    }
+
+    /// First cell along [Grid::ray] for which `hit` returns true
+    pub fn cast_ray(
+        &self,
+        start: impl Into<Vec2D>,
+        step: impl Into<Vec2D>,
+        hit: impl Fn(&T) -> bool,
+    ) -> Option<(Vec2D, &T)> {
+        self.ray(start, step).find(|(_, val)| hit(val))
+    }
+
+    /// For each of the 8 directions, the first cell seen from `pos` for which `is_visible` returns
+    /// true. Directions in which nothing is visible are skipped.
+    pub fn first_visible<'a>(
+        &'a self,
+        pos: impl Into<Vec2D>,
+        is_visible: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Vec2D, &'a T)> + 'a {
+        let pos = pos.into();
+        Vec2D::zero()
+            .all_neighbors()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(move |dir| self.cast_ray(pos, dir, &is_visible))
+    }
+
+    /// Whether no cell exactly on the straight line between `from` and `to` (both excluded) is
+    /// blocking. Only cells whose centers are exactly on the line are checked, which is what
+    /// asteroid-style puzzles expect.
+    pub fn line_of_sight(
+        &self,
+        from: impl Into<Vec2D>,
+        to: impl Into<Vec2D>,
+        is_blocking: impl Fn(&T) -> bool,
+    ) -> bool {
+        let (from, to) = (from.into(), to.into());
+        let delta = to - from;
+        if delta.is_zero() {
+            return true;
+        }
+
+        let steps = num::integer::gcd(delta.x, delta.y);
+        let step = Vec2D::new(delta.x / steps, delta.y / steps);
+        self.ray(from, step).take(steps as usize - 1).all(|(_, val)| !is_blocking(val))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::Input;
+
+    use super::*;
+
+    #[test]
+    fn ray() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6], [7, 8, 9]].into();
+        assert_eq!(grid.ray((0, 0), (1, 1)).map(|(_, v)| *v).collect_vec(), vec![5, 9]);
+        assert_eq!(grid.ray((2, 1), (-1, 0)).map(|(_, v)| *v).collect_vec(), vec![5, 4]);
+        assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+        assert_eq!(grid.cast_ray((0, 2), (1, -1), |&v| v > 4), Some((Vec2D::new(1, 1), &5)));
+        assert_eq!(grid.cast_ray((0, 2), (1, -1), |&v| v > 9), None);
+    }
+
+    #[test]
+    fn seats() {
+        let grid = Input::from(
+            "
+.......#.
+...#.....
+.#.......
+.........
+..#L....#
+....#....
+.........
+#........
+...#.....",
+        )
+        .char_grid();
+        assert_eq!(grid.first_visible((3, 4), |&c| c != '.').filter(|(_, &c)| c == '#').count(), 8);
+
+        let grid = Input::from(".............\n.L.L.#.#.#.#.\n.............").char_grid();
+        assert_eq!(
+            grid.first_visible((1, 1), |&c| c != '.').collect_vec(),
+            vec![(Vec2D::new(3, 1), &'L')]
+        );
+    }
+
+    #[test]
+    fn asteroids() {
+        let grid = Input::from(".#..#\n.....\n#####\n....#\n...##").char_grid();
+        let asteroids = grid.iter().filter(|(_, &c)| c == '#').map(|(pos, _)| pos).collect_vec();
+        let visible = |from: Vec2D| {
+            asteroids
+                .iter()
+                .filter(|&&to| to != from && grid.line_of_sight(from, to, |&c| c == '#'))
+                .count()
+        };
+        assert_eq!(visible(Vec2D::new(3, 4)), 8);
+        assert_eq!(visible(Vec2D::new(1, 0)), 7);
+        assert_eq!(visible(Vec2D::new(4, 2)), 5);
+        assert_eq!(asteroids.iter().map(|&pos| visible(pos)).max(), Some(8));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::math::Vec2D;
+
+use super::Grid;
+
+/// A reflection axis found by [Grid::horizontal_reflections] or [Grid::vertical_reflections]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    /// Number of rows above (or columns left of) the axis
+    pub axis: usize,
+    /// Pairs of mirrored cells that differ
+    pub mismatches: Vec<(Vec2D, Vec2D)>,
+}
+
+impl<T: Eq> Grid<T> {
+    /// All axes between two rows across which the grid is mirrored, with exactly `mismatches`
+    /// pairs of mirrored cells differing. Rows without a mirrored counterpart are ignored.
+    pub fn horizontal_reflections(&self, mismatches: usize) -> Vec<Reflection> {
+        self.reflections(self.num_rows(), self.num_cols(), mismatches, |line, i| (i, line))
+    }
+
+    /// Same as [Grid::horizontal_reflections], but with axes between two columns
+    pub fn vertical_reflections(&self, mismatches: usize) -> Vec<Reflection> {
+        self.reflections(self.num_cols(), self.num_rows(), mismatches, |line, i| (line, i))
+    }
+
+    fn reflections(
+        &self,
+        num_lines: usize,
+        line_len: usize,
+        allowed: usize,
+        pos: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Vec<Reflection> {
+        (1..num_lines)
+            .filter_map(|axis| {
+                let mut mismatches = Vec::new();
+                for offset in 0..axis.min(num_lines - axis) {
+                    let (before, after) = (axis - 1 - offset, axis + offset);
+                    for i in 0..line_len {
+                        let (lhs, rhs) = (pos(before, i), pos(after, i));
+                        if self[lhs] != self[rhs] {
+                            if mismatches.len() == allowed {
+                                return None;
+                            }
+                            mismatches.push((lhs.into(), rhs.into()));
+                        }
+                    }
+                }
+                (mismatches.len() == allowed).then_some(Reflection { axis, mismatches })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    #[test]
+    fn mirrors() {
+        let first = Input::from(
+            "
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.",
+        )
+        .char_grid();
+        let second = Input::from(
+            "
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#",
+        )
+        .char_grid();
+
+        assert_eq!(first.vertical_reflections(0), [Reflection { axis: 5, mismatches: vec![] }]);
+        assert!(first.horizontal_reflections(0).is_empty());
+        assert_eq!(second.horizontal_reflections(0), [Reflection { axis: 4, mismatches: vec![] }]);
+        assert!(second.vertical_reflections(0).is_empty());
+
+        assert_eq!(
+            first.horizontal_reflections(1),
+            [Reflection { axis: 3, mismatches: vec![((0, 0).into(), (0, 5).into())] }]
+        );
+        assert!(first.vertical_reflections(1).is_empty());
+        assert_eq!(
+            second.horizontal_reflections(1),
+            [Reflection { axis: 1, mismatches: vec![((4, 0).into(), (4, 1).into())] }]
+        );
+    }
+}
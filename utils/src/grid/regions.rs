@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+
+use crate::math::{Box2D, Vec2D};
+
+use super::Grid;
+
+/// Orthogonally connected cells that all have the same value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<'a, T> {
+    pub value: &'a T,
+    pub cells: HashSet<Vec2D>,
+}
+
+impl<T> Region<'_, T> {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges between this region and other regions (or the outside of the grid)
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|cell| cell.orthogonal_neighbors())
+            .filter(|neighbor| !self.cells.contains(neighbor))
+            .count()
+    }
+
+    /// Number of straight sides of the region's outline (including the outline of holes)
+    pub fn sides(&self) -> usize {
+        // A polygon has as many sides as it has corners, and corners are much easier to count
+        self.cells
+            .iter()
+            .map(|&cell| {
+                [(1, 1), (1, -1), (-1, 1), (-1, -1)]
+                    .into_iter()
+                    .filter(|&(x, y)| {
+                        let horizontal = self.cells.contains(&(cell + (x, 0)));
+                        let vertical = self.cells.contains(&(cell + (0, y)));
+                        let diagonal = self.cells.contains(&(cell + (x, y)));
+                        let convex = !horizontal && !vertical;
+                        let concave = horizontal && vertical && !diagonal;
+                        convex || concave
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    pub fn bounding_box(&self) -> Box2D {
+        self.cells.iter().copied().collect()
+    }
+}
+
+impl<T: Eq> Grid<T> {
+    /// Assigns every cell the index of its region, where a region is a set of orthogonally
+    /// connected cells with equal values. Indices start at 0 and follow the row-major order of
+    /// the first cell of each region. Also returns the number of regions.
+    pub fn region_labels(&self) -> (Grid<usize>, usize) {
+        let mut labels: Grid<Option<usize>> = self.map(|_| None);
+        let mut num_regions = 0;
+
+        for start in self.coordinates_row_major() {
+            if labels[start].is_some() {
+                continue;
+            }
+            labels[start] = Some(num_regions);
+            let mut stack = vec![start];
+            while let Some(pos) = stack.pop() {
+                for neighbor in self.orthogonal_neighbors(&pos) {
+                    if labels[neighbor].is_none() && self[neighbor] == self[pos] {
+                        labels[neighbor] = Some(num_regions);
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            num_regions += 1;
+        }
+
+        (labels.map(|label| label.unwrap()), num_regions)
+    }
+
+    /// All regions of orthogonally connected cells with equal values, see
+    /// [Grid::region_labels] for the order
+    pub fn regions(&self) -> Vec<Region<'_, T>> {
+        let (labels, num_regions) = self.region_labels();
+        let mut regions: Vec<Option<Region<'_, T>>> = (0..num_regions).map(|_| None).collect();
+        for (pos, &label) in labels.iter() {
+            regions[label]
+                .get_or_insert_with(|| Region { value: &self[pos], cells: HashSet::new() })
+                .cells
+                .insert(pos);
+        }
+        regions.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    fn price(grid: &Grid<char>) -> (usize, usize) {
+        let regions = grid.regions();
+        (
+            regions.iter().map(|region| region.area() * region.perimeter()).sum(),
+            regions.iter().map(|region| region.area() * region.sides()).sum(),
+        )
+    }
+
+    #[test]
+    fn regions() {
+        let grid = Input::from("AAAA\nBBCD\nBBCC\nEEEC").char_grid();
+        let (labels, num_regions) = grid.region_labels();
+        assert_eq!(num_regions, 5);
+        assert_eq!(labels, Grid::from([[0, 0, 0, 0], [1, 1, 2, 3], [1, 1, 2, 2], [4, 4, 4, 2]]));
+
+        let regions = grid.regions();
+        let c = &regions[2];
+        assert_eq!(c.value, &'C');
+        assert_eq!(c.area(), 4);
+        assert_eq!(c.perimeter(), 10);
+        assert_eq!(c.sides(), 8);
+        assert_eq!(c.bounding_box(), Box2D::new((2, 1), (3, 3)));
+        assert_eq!(price(&grid), (140, 80));
+    }
+
+    #[test]
+    fn holes() {
+        let grid = Input::from("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO").char_grid();
+        assert_eq!(grid.regions().len(), 5);
+        assert_eq!(price(&grid), (772, 436));
+
+        let grid = Input::from("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE").char_grid();
+        assert_eq!(price(&grid).1, 236);
+
+        let grid = Input::from("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA").char_grid();
+        assert_eq!(price(&grid).1, 368);
+    }
+
+    #[test]
+    fn larger_example() {
+        let grid = Input::from(
+            "
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE",
+        )
+        .char_grid();
+        assert_eq!(price(&grid), (1930, 1206));
+    }
+}
@@ -0,0 +1,232 @@
+use itertools::Itertools;
+
+use crate::math::Vec2D;
+
+use super::Grid;
+
+impl<T> Grid<T> {
+    /// Insert a row so that it has index `index` afterwards
+    pub fn insert_row(&mut self, index: usize, row: impl IntoIterator<Item = T>) {
+        assert!(index <= self.num_rows);
+        let start = index * self.num_cols;
+        let len_before = self.data.len();
+        self.data.splice(start..start, row);
+        let row_len = self.data.len() - len_before;
+        if self.num_rows == 0 {
+            self.num_cols = row_len;
+        }
+        assert_eq!(row_len, self.num_cols, "Row must have as many cells as the grid has columns");
+        self.num_rows += 1;
+    }
+
+    pub fn remove_row(&mut self, index: usize) -> Vec<T> {
+        assert!(index < self.num_rows);
+        let start = index * self.num_cols;
+        self.num_rows -= 1;
+        self.data.drain(start..start + self.num_cols).collect()
+    }
+
+    /// Insert a column so that it has index `index` afterwards
+    pub fn insert_col(&mut self, index: usize, col: impl IntoIterator<Item = T>) {
+        assert!(index <= self.num_cols);
+        let mut col = col.into_iter().collect_vec().into_iter();
+        assert_eq!(col.len(), self.num_rows, "Column must have as many cells as the grid has rows");
+
+        let num_cols = self.num_cols;
+        let mut data = Vec::with_capacity(self.data.len() + self.num_rows);
+        for (i, val) in std::mem::take(&mut self.data).into_iter().enumerate() {
+            if i % num_cols == index {
+                data.extend(col.next());
+            }
+            data.push(val);
+            if index == num_cols && i % num_cols == num_cols - 1 {
+                data.extend(col.next());
+            }
+        }
+        if num_cols == 0 {
+            data.extend(col);
+        }
+        self.data = data;
+        self.num_cols += 1;
+    }
+
+    pub fn remove_col(&mut self, index: usize) -> Vec<T> {
+        assert!(index < self.num_cols);
+        let num_cols = self.num_cols;
+        let (removed, data) =
+            std::mem::take(&mut self.data).into_iter().enumerate().partition_map(|(i, val)| {
+                if i % num_cols == index {
+                    itertools::Either::Left(val)
+                } else {
+                    itertools::Either::Right(val)
+                }
+            });
+        self.data = data;
+        self.num_cols -= 1;
+        if self.num_cols == 0 {
+            self.num_rows = 0;
+        }
+        removed
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Insert a copy of every row for which `predicate` returns true directly after that row
+    pub fn duplicate_rows(&mut self, predicate: impl Fn(&[T]) -> bool) {
+        for row in (0..self.num_rows).rev() {
+            let values = self.row_slice(row);
+            if predicate(values) {
+                let copy = values.to_vec();
+                self.insert_row(row + 1, copy);
+            }
+        }
+    }
+
+    /// Insert a copy of every column for which `predicate` returns true directly after that
+    /// column
+    pub fn duplicate_cols(&mut self, predicate: impl Fn(&[T]) -> bool) {
+        for col in (0..self.num_cols).rev() {
+            let values = self.col_values(col).cloned().collect_vec();
+            if predicate(&values) {
+                self.insert_col(col + 1, values);
+            }
+        }
+    }
+
+    /// Expansion where every row and column that only consists of cells for which `is_empty`
+    /// returns true is replaced by `factor` copies of itself. Panics if `factor` is 0.
+    pub fn expansion(&self, is_empty: impl Fn(&T) -> bool, factor: usize) -> Expansion {
+        assert!(factor >= 1, "expansion factor must be at least 1");
+        let empty_before = |is_line_empty: Vec<bool>| {
+            is_line_empty
+                .into_iter()
+                .scan(0, |count, empty| {
+                    let before = *count;
+                    *count += usize::from(empty);
+                    Some(before)
+                })
+                .collect()
+        };
+        Expansion {
+            empty_rows_before: empty_before(
+                self.rows().map(|mut row| row.all(|(_, val)| is_empty(val))).collect(),
+            ),
+            empty_cols_before: empty_before(
+                self.cols().map(|mut col| col.all(|(_, val)| is_empty(val))).collect(),
+            ),
+            factor,
+        }
+    }
+}
+
+/// Maps positions of a grid to their positions after expanding some rows and columns, without
+/// actually building the expanded grid. Created by [Grid::expansion].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion {
+    empty_rows_before: Vec<usize>,
+    empty_cols_before: Vec<usize>,
+    factor: usize,
+}
+
+impl Expansion {
+    pub fn map(&self, pos: impl Into<Vec2D>) -> Vec2D {
+        let pos = pos.into();
+        let extra = |empty_before: &Vec<usize>, i: i64| {
+            (empty_before[i as usize] * (self.factor - 1)) as i64
+        };
+        Vec2D::new(
+            pos.x + extra(&self.empty_cols_before, pos.x),
+            pos.y + extra(&self.empty_rows_before, pos.y),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+
+        grid.insert_row(1, [7, 8, 9]);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [7, 8, 9], [4, 5, 6]]));
+        assert_eq!(grid.remove_row(0), vec![1, 2, 3]);
+        assert_eq!(grid, Grid::from([[7, 8, 9], [4, 5, 6]]));
+        grid.insert_row(2, [0, 0, 0]);
+        assert_eq!(grid, Grid::from([[7, 8, 9], [4, 5, 6], [0, 0, 0]]));
+
+        grid.insert_col(0, [1, 2, 3]);
+        assert_eq!(grid, Grid::from([[1, 7, 8, 9], [2, 4, 5, 6], [3, 0, 0, 0]]));
+        grid.insert_col(4, [5, 5, 5]);
+        assert_eq!(grid, Grid::from([[1, 7, 8, 9, 5], [2, 4, 5, 6, 5], [3, 0, 0, 0, 5]]));
+        assert_eq!(grid.remove_col(2), vec![8, 5, 0]);
+        assert_eq!(grid, Grid::from([[1, 7, 9, 5], [2, 4, 6, 5], [3, 0, 0, 5]]));
+        assert_eq!((grid.num_rows(), grid.num_cols()), (3, 4));
+
+        let mut grid: Grid<_> = [[1], [2]].into();
+        grid.remove_col(0);
+        assert_eq!((grid.num_rows(), grid.num_cols()), (0, 0));
+        grid.insert_row(0, [1, 2]);
+        assert_eq!(grid, Grid::from([[1, 2]]));
+    }
+
+    fn galaxies() -> Grid<char> {
+        Input::from(
+            "
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....",
+        )
+        .char_grid()
+    }
+
+    fn sum_of_distances(positions: impl Iterator<Item = Vec2D>) -> usize {
+        positions
+            .collect_vec()
+            .into_iter()
+            .tuple_combinations()
+            .map(|(a, b)| (a - b).manhattan_dist())
+            .sum()
+    }
+
+    #[test]
+    fn expand() {
+        let grid = galaxies();
+        let galaxies = || grid.iter().filter(|(_, &c)| c == '#').map(|(pos, _)| pos);
+
+        let mut expanded = grid.clone();
+        expanded.duplicate_rows(|row| row.iter().all(|&c| c == '.'));
+        expanded.duplicate_cols(|col| col.iter().all(|&c| c == '.'));
+        assert_eq!((expanded.num_rows(), expanded.num_cols()), (12, 13));
+
+        let expansion = grid.expansion(|&c| c == '.', 2);
+        assert_eq!(expansion.map((3, 0)), Vec2D::new(4, 0));
+        assert_eq!(expansion.map((0, 9)), Vec2D::new(0, 11));
+        assert_eq!(
+            galaxies().map(|pos| expansion.map(pos)).sorted().collect_vec(),
+            expanded.iter().filter(|(_, &c)| c == '#').map(|(pos, _)| pos).sorted().collect_vec()
+        );
+
+        assert_eq!(sum_of_distances(galaxies().map(|pos| expansion.map(pos))), 374);
+        let expansion = grid.expansion(|&c| c == '.', 10);
+        assert_eq!(sum_of_distances(galaxies().map(|pos| expansion.map(pos))), 1030);
+        let expansion = grid.expansion(|&c| c == '.', 100);
+        assert_eq!(sum_of_distances(galaxies().map(|pos| expansion.map(pos))), 8410);
+    }
+
+    #[test]
+    #[should_panic]
+    fn expand_by_zero() {
+        galaxies().expansion(|&c| c == '.', 0);
+    }
+}
@@ -0,0 +1,140 @@
+use std::ops::Index;
+
+use crate::{graphs::UnweightedGraph, math::Vec2D};
+
+use super::Grid;
+
+/// View of a [Grid] that repeats infinitely in all directions. The original grid is the tile at
+/// (0, 0).
+///
+/// Also implements [UnweightedGraph] with orthogonal neighbors, where only cells for which the
+/// `passable` predicate returns true can be entered (all cells by default, see
+/// [TiledGrid::passable]).
+pub struct TiledGrid<'a, T, P = fn(&T) -> bool> {
+    grid: &'a Grid<T>,
+    passable: P,
+}
+
+impl<T> Grid<T> {
+    pub fn tiled(&self) -> TiledGrid<'_, T> {
+        TiledGrid { grid: self, passable: |_| true }
+    }
+}
+
+impl<'a, T, P> TiledGrid<'a, T, P> {
+    pub fn passable<F: Fn(&T) -> bool>(self, passable: F) -> TiledGrid<'a, T, F> {
+        TiledGrid { grid: self.grid, passable }
+    }
+
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    pub fn get(&self, pos: impl Into<Vec2D>) -> &'a T {
+        &self.grid[self.local(pos)]
+    }
+
+    /// Which copy of the grid `pos` lies in
+    pub fn tile_of(&self, pos: impl Into<Vec2D>) -> Vec2D {
+        let pos = pos.into();
+        Vec2D::new(
+            pos.x.div_euclid(self.grid.num_cols() as i64),
+            pos.y.div_euclid(self.grid.num_rows() as i64),
+        )
+    }
+
+    /// Position inside the original grid that `pos` is a copy of
+    pub fn local(&self, pos: impl Into<Vec2D>) -> Vec2D {
+        let pos = pos.into();
+        Vec2D::new(
+            pos.x.rem_euclid(self.grid.num_cols() as i64),
+            pos.y.rem_euclid(self.grid.num_rows() as i64),
+        )
+    }
+
+    pub fn orthogonal_neighbor_values<'b>(
+        &'b self,
+        pos: &'b Vec2D,
+    ) -> impl Iterator<Item = (Vec2D, &'a T)> + 'b {
+        pos.orthogonal_neighbors().map(|neighbor| (neighbor, self.get(neighbor)))
+    }
+
+    pub fn all_neighbor_values<'b>(
+        &'b self,
+        pos: &'b Vec2D,
+    ) -> impl Iterator<Item = (Vec2D, &'a T)> + 'b {
+        pos.all_neighbors().map(|neighbor| (neighbor, self.get(neighbor)))
+    }
+}
+
+impl<T, P, Pos> Index<Pos> for TiledGrid<'_, T, P>
+where
+    Pos: Into<Vec2D>,
+{
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+    }
+}
+
+impl<T, P: Fn(&T) -> bool> UnweightedGraph for TiledGrid<'_, T, P> {
+    type Node = Vec2D;
+
+    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
+        node.orthogonal_neighbors().filter(|neighbor| (self.passable)(self.get(*neighbor)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{graphs::reachable_in_exactly, Input};
+
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        let tiled = grid.tiled();
+        assert_eq!(tiled[(0, 0)], 1);
+        assert_eq!(tiled[(4, 3)], 5);
+        assert_eq!(tiled[(-1, -1)], 6);
+        assert_eq!(tiled.local((-4, 5)), Vec2D::new(2, 1));
+        assert_eq!(tiled.tile_of((2, 1)), Vec2D::new(0, 0));
+        assert_eq!(tiled.tile_of((3, 2)), Vec2D::new(1, 1));
+        assert_eq!(tiled.tile_of((-1, -3)), Vec2D::new(-1, -2));
+        assert_eq!(tiled.orthogonal_neighbor_values(&Vec2D::new(0, 0)).count(), 4);
+    }
+
+    #[test]
+    fn infinite_garden() {
+        let grid = Input::from(
+            "
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........",
+        )
+        .char_grid();
+        let start = grid.find_position(&'S').unwrap();
+        let garden = grid.tiled().passable(|&c| c != '#');
+
+        assert_eq!(reachable_in_exactly(&garden, start, 6).len(), 16);
+        assert_eq!(reachable_in_exactly(&garden, start, 10).len(), 50);
+        assert_eq!(reachable_in_exactly(&garden, start, 50).len(), 1594);
+        assert_eq!(reachable_in_exactly(&garden, start, 100).len(), 6536);
+
+        let tiles = reachable_in_exactly(&garden, start, 50)
+            .into_iter()
+            .map(|pos| garden.tile_of(pos))
+            .collect::<std::collections::HashSet<_>>();
+        assert!(tiles.contains(&Vec2D::new(-4, 0)) && tiles.contains(&Vec2D::new(4, 0)));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::math::{Direction, Vec2D};
+
+use super::Grid;
+
+impl<T> Grid<T> {
+    /// Slide all cells for which `is_movable` returns true as far as possible in `dir`, until they
+    /// hit the edge of the grid, a cell for which `is_blocker` returns true, or another movable
+    /// cell that has already stopped. All other cells are treated as empty space, and are swapped
+    /// with the cells moving through them.
+    ///
+    /// Runs in O(number of cells). Returns `(from, to)` for every cell that moved.
+    pub fn tilt(
+        &mut self,
+        dir: Direction,
+        is_movable: impl Fn(&T) -> bool,
+        is_blocker: impl Fn(&T) -> bool,
+    ) -> Vec<(Vec2D, Vec2D)> {
+        let (num_cols, num_rows) = (self.num_cols as i64, self.num_rows as i64);
+        let edge: Vec<Vec2D> = match dir {
+            Direction::Up => (0..num_cols).map(|x| Vec2D::new(x, 0)).collect(),
+            Direction::Down => (0..num_cols).map(|x| Vec2D::new(x, num_rows - 1)).collect(),
+            Direction::Left => (0..num_rows).map(|y| Vec2D::new(0, y)).collect(),
+            Direction::Right => (0..num_rows).map(|y| Vec2D::new(num_cols - 1, y)).collect(),
+        };
+        let scan = Vec2D::from(dir.opposite());
+
+        let mut moved = Vec::new();
+        for start in edge {
+            let mut target = start;
+            let mut pos = start;
+            while self.contains(&pos) {
+                let index = self.index_of(&pos);
+                if is_blocker(&self.data[index]) {
+                    target = pos + scan;
+                } else if is_movable(&self.data[index]) {
+                    if pos != target {
+                        let target_index = self.index_of(&target);
+                        self.data.swap(index, target_index);
+                        moved.push((pos, target));
+                    }
+                    target += scan;
+                }
+                pos += scan;
+            }
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    fn platform() -> Grid<char> {
+        Input::from(
+            "
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....",
+        )
+        .char_grid()
+    }
+
+    fn tilt(grid: &mut Grid<char>, dir: Direction) -> Vec<(Vec2D, Vec2D)> {
+        grid.tilt(dir, |&c| c == 'O', |&c| c == '#')
+    }
+
+    #[test]
+    fn tilt_north() {
+        let mut grid = platform();
+        let moved = tilt(&mut grid, Direction::Up);
+        assert_eq!(
+            grid,
+            Input::from(
+                "
+OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....",
+            )
+            .char_grid()
+        );
+        let load: usize = grid
+            .iter()
+            .filter(|(_, &c)| c == 'O')
+            .map(|(pos, _)| grid.num_rows() - pos.y as usize)
+            .sum();
+        assert_eq!(load, 136);
+
+        assert!(moved.contains(&(Vec2D::new(2, 1), Vec2D::new(2, 0))));
+        assert!(moved.contains(&(Vec2D::new(1, 9), Vec2D::new(1, 2))));
+        assert!(!moved.iter().any(|(from, _)| *from == Vec2D::new(0, 0)));
+        assert!(tilt(&mut grid, Direction::Up).is_empty());
+    }
+
+    #[test]
+    fn spin_cycle() {
+        let mut grid = platform();
+        for dir in [Direction::Up, Direction::Left, Direction::Down, Direction::Right] {
+            tilt(&mut grid, dir);
+        }
+        assert_eq!(
+            grid,
+            Input::from(
+                "
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....",
+            )
+            .char_grid()
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::math::Vec2D;
+
+use super::Grid;
+
+/// Whole-grid transformations. The in-place versions don't require `T: Clone`, the copying
+/// versions (`transposed`, `rotated_right`, ...) do.
+impl<T> Grid<T> {
+    /// Move every cell to a new position, possibly changing the shape of the grid
+    fn permute(&mut self, num_rows: usize, num_cols: usize, new_pos: impl Fn(Vec2D) -> Vec2D) {
+        let mut data: Vec<Option<T>> =
+            std::iter::repeat_with(|| None).take(self.data.len()).collect();
+        let old_num_cols = self.num_cols;
+        for (index, val) in std::mem::take(&mut self.data).into_iter().enumerate() {
+            let pos = new_pos(Vec2D::from((index % old_num_cols, index / old_num_cols)));
+            data[pos.y as usize * num_cols + pos.x as usize] = Some(val);
+        }
+        self.data = data.into_iter().map(Option::unwrap).collect();
+        self.num_rows = num_rows;
+        self.num_cols = num_cols;
+    }
+
+    /// Mirror along the main diagonal, i.e. swap rows and columns
+    pub fn transpose(&mut self) {
+        self.permute(self.num_cols, self.num_rows, |pos| pos.flipped());
+    }
+
+    /// Rotate by 90 degrees clockwise
+    pub fn rotate_right(&mut self) {
+        let num_rows = self.num_rows as i64;
+        self.permute(self.num_cols, self.num_rows, |pos| Vec2D::new(num_rows - 1 - pos.y, pos.x));
+    }
+
+    /// Rotate by 90 degrees counterclockwise
+    pub fn rotate_left(&mut self) {
+        let num_cols = self.num_cols as i64;
+        self.permute(self.num_cols, self.num_rows, |pos| Vec2D::new(pos.y, num_cols - 1 - pos.x));
+    }
+
+    pub fn rotate_180(&mut self) {
+        self.data.reverse();
+    }
+
+    /// Mirror left to right, i.e. reverse each row
+    pub fn flip_horizontal(&mut self) {
+        for row in self.data.chunks_exact_mut(self.num_cols.max(1)) {
+            row.reverse();
+        }
+    }
+
+    /// Mirror top to bottom, i.e. reverse the order of the rows
+    pub fn flip_vertical(&mut self) {
+        self.rotate_180();
+        self.flip_horizontal();
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    fn transformed(&self, transform: impl FnOnce(&mut Self)) -> Self {
+        let mut grid = self.clone();
+        transform(&mut grid);
+        grid
+    }
+
+    pub fn transposed(&self) -> Self {
+        self.transformed(Self::transpose)
+    }
+
+    pub fn rotated_right(&self) -> Self {
+        self.transformed(Self::rotate_right)
+    }
+
+    pub fn rotated_left(&self) -> Self {
+        self.transformed(Self::rotate_left)
+    }
+
+    pub fn rotated_180(&self) -> Self {
+        self.transformed(Self::rotate_180)
+    }
+
+    pub fn flipped_horizontal(&self) -> Self {
+        self.transformed(Self::flip_horizontal)
+    }
+
+    pub fn flipped_vertical(&self) -> Self {
+        self.transformed(Self::flip_vertical)
+    }
+
+    /// All 8 rotations and reflections of the grid (the dihedral group), starting with the grid
+    /// itself. Symmetric grids will yield some duplicates.
+    pub fn symmetries(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..8).map(move |i| {
+            let mut grid = self.clone();
+            if i >= 4 {
+                grid.flip_horizontal();
+            }
+            for _ in 0..i % 4 {
+                grid.rotate_right();
+            }
+            grid
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn transpose() {
+        let mut grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        assert_eq!(grid.transposed(), Grid::from([[1, 4], [2, 5], [3, 6]]));
+        grid.transpose();
+        assert_eq!(grid, Grid::from([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!((grid.num_rows(), grid.num_cols()), (3, 2));
+    }
+
+    #[test]
+    fn rotate() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        assert_eq!(grid.rotated_right(), Grid::from([[4, 1], [5, 2], [6, 3]]));
+        assert_eq!(grid.rotated_left(), Grid::from([[3, 6], [2, 5], [1, 4]]));
+        assert_eq!(grid.rotated_180(), Grid::from([[6, 5, 4], [3, 2, 1]]));
+        assert_eq!(grid.rotated_right().rotated_right(), grid.rotated_180());
+        assert_eq!(grid.rotated_right().rotated_left(), grid);
+
+        // Works without Clone
+        #[derive(Debug, PartialEq)]
+        struct NoClone(i32);
+        let mut grid: Grid<_> = [[NoClone(1), NoClone(2)]].into();
+        grid.rotate_right();
+        assert_eq!(grid, Grid::from([[NoClone(1)], [NoClone(2)]]));
+    }
+
+    #[test]
+    fn flip() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        assert_eq!(grid.flipped_horizontal(), Grid::from([[3, 2, 1], [6, 5, 4]]));
+        assert_eq!(grid.flipped_vertical(), Grid::from([[4, 5, 6], [1, 2, 3]]));
+    }
+
+    #[test]
+    fn symmetries() {
+        let grid: Grid<_> = [[1, 2], [3, 4]].into();
+        let symmetries: Vec<_> = grid.symmetries().collect();
+        assert_eq!(symmetries.len(), 8);
+        assert_eq!(symmetries[0], grid);
+        assert_eq!(symmetries.iter().collect::<HashSet<_>>().len(), 8);
+        assert!(symmetries.contains(&grid.transposed()));
+        assert!(symmetries.contains(&grid.flipped_vertical()));
+
+        let symmetric: Grid<_> = [[1, 1], [1, 1]].into();
+        assert_eq!(symmetric.symmetries().collect::<HashSet<_>>().len(), 1);
+    }
+}
@@ -0,0 +1,315 @@
+use std::ops::{Index, Range};
+
+use itertools::Itertools;
+
+use crate::math::{Box2D, Vec2D};
+
+use super::Grid;
+
+/// Borrowed rectangular part of a [Grid]. All positions are relative to the top left corner of
+/// the view.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    offset: Vec2D,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> Grid<T> {
+    /// View of the cells inside `area` (inclusive). Panics if `area` is not inside the grid.
+    pub fn view(&self, area: &Box2D) -> GridView<'_, T> {
+        GridView::new(self, Vec2D::zero(), self.num_rows, self.num_cols).view(area)
+    }
+
+    /// View of the cells inside the given ranges, which may be empty. Panics if the ranges are not
+    /// inside the grid.
+    pub fn view_ranges(&self, rows: Range<usize>, cols: Range<usize>) -> GridView<'_, T> {
+        assert!(
+            rows.start <= rows.end && rows.end <= self.num_rows,
+            "rows {rows:?} are not inside the grid"
+        );
+        assert!(
+            cols.start <= cols.end && cols.end <= self.num_cols,
+            "cols {cols:?} are not inside the grid"
+        );
+        GridView::new(self, Vec2D::from((cols.start, rows.start)), rows.len(), cols.len())
+    }
+
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView::new(self, Vec2D::zero(), self.num_rows, self.num_cols)
+    }
+
+    /// All `size`x`size` views in row-major order, overlapping each other, together with the
+    /// position of their top left corner
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = (Vec2D, GridView<'_, T>)> + '_ {
+        assert!(size > 0, "window size must be positive");
+        let rows = (self.num_rows + 1).saturating_sub(size);
+        let cols = (self.num_cols + 1).saturating_sub(size);
+        (0..rows).cartesian_product(0..cols).map(move |(y, x)| {
+            let pos = Vec2D::from((x, y));
+            (pos, GridView::new(self, pos, size, size))
+        })
+    }
+
+    /// Split the grid into non-overlapping `size`x`size` tiles in row-major order, together
+    /// with the index of the tile (i.e. the tile at (1, 0) starts at (size, 0)).
+    /// Panics if the grid can't be split evenly.
+    pub fn tiles(&self, size: usize) -> impl Iterator<Item = (Vec2D, GridView<'_, T>)> + '_ {
+        assert!(size > 0, "tile size must be positive");
+        assert!(self.num_rows.is_multiple_of(size) && self.num_cols.is_multiple_of(size));
+        (0..self.num_rows / size).cartesian_product(0..self.num_cols / size).map(move |(y, x)| {
+            let tile = Vec2D::from((x, y));
+            (tile, GridView::new(self, tile * size, size, size))
+        })
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    fn new(grid: &'a Grid<T>, offset: Vec2D, num_rows: usize, num_cols: usize) -> Self {
+        GridView { grid, offset, num_rows, num_cols }
+    }
+
+    /// View of a part of this view, `area` is relative to this view
+    pub fn view(&self, area: &Box2D) -> GridView<'a, T> {
+        assert!(
+            self.contains(&area.lower) && self.contains(&area.upper),
+            "{area:?} is not inside the grid"
+        );
+        assert!(
+            area.lower.x <= area.upper.x && area.lower.y <= area.upper.y,
+            "{area:?} has its lower corner after its upper corner"
+        );
+        let size = area.upper - area.lower + (1, 1);
+        GridView::new(self.grid, self.offset + area.lower, size.y as usize, size.x as usize)
+    }
+
+    /// Position of the top left corner of the view inside the grid
+    pub fn offset(&self) -> Vec2D {
+        self.offset
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn contains(&self, pos: &Vec2D) -> bool {
+        pos.x >= 0 && pos.y >= 0 && pos.x < self.num_cols as i64 && pos.y < self.num_rows as i64
+    }
+
+    pub fn get(&self, pos: impl Into<Vec2D>) -> Option<&'a T> {
+        let pos = pos.into();
+        self.contains(&pos).then(|| &self.grid[self.offset + pos])
+    }
+
+    pub fn row(&self, row: usize) -> impl DoubleEndedIterator<Item = (Vec2D, &'a T)> + 'a {
+        assert!(row < self.num_rows, "row {row} out of bounds");
+        let start = self.offset.x as usize;
+        self.grid.row_slice(self.offset.y as usize + row)[start..start + self.num_cols]
+            .iter()
+            .enumerate()
+            .map(move |(col, item)| ((col, row).into(), item))
+    }
+
+    pub fn rows(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = (Vec2D, &'a T)>> + 'a {
+        let view = *self;
+        (0..self.num_rows).map(move |row| view.row(row))
+    }
+
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = (Vec2D, &'a T)> + 'a {
+        assert!(col < self.num_cols, "column {col} out of bounds");
+        let view = *self;
+        (0..self.num_rows).map(move |row| {
+            let pos = Vec2D::from((col, row));
+            (pos, &view.grid[view.offset + pos])
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D, &'a T)> + 'a {
+        self.rows().flatten()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().map(|(_, val)| val)
+    }
+
+    pub fn orthogonal_neighbors<'b>(&'b self, pos: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'b {
+        pos.orthogonal_neighbors().filter(move |neighbor| self.contains(neighbor))
+    }
+
+    pub fn orthogonal_neighbor_values<'b>(
+        &'b self,
+        pos: &'b Vec2D,
+    ) -> impl Iterator<Item = &'a T> + 'b {
+        self.orthogonal_neighbors(pos).map(|neighbor| self.get(neighbor).unwrap())
+    }
+
+    pub fn diagonal_neighbors<'b>(&'b self, pos: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'b {
+        pos.diagonal_neighbors().filter(move |neighbor| self.contains(neighbor))
+    }
+
+    pub fn diagonal_neighbor_values<'b>(
+        &'b self,
+        pos: &'b Vec2D,
+    ) -> impl Iterator<Item = &'a T> + 'b {
+        self.diagonal_neighbors(pos).map(|neighbor| self.get(neighbor).unwrap())
+    }
+
+    pub fn all_neighbors<'b>(&'b self, pos: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'b {
+        pos.all_neighbors().filter(move |neighbor| self.contains(neighbor))
+    }
+
+    pub fn all_neighbor_values<'b>(&'b self, pos: &'b Vec2D) -> impl Iterator<Item = &'a T> + 'b {
+        self.all_neighbors(pos).map(|neighbor| self.get(neighbor).unwrap())
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copy the view into its own grid
+    pub fn to_grid(&self) -> Grid<T> {
+        self.rows().map(|row| row.map(|(_, val)| val.clone())).collect()
+    }
+}
+
+impl<T, Pos> Index<Pos> for GridView<'_, T>
+where
+    Pos: Into<Vec2D>,
+{
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos.into()).unwrap()
+    }
+}
+
+impl<T: PartialEq> PartialEq for GridView<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_rows == other.num_rows
+            && self.num_cols == other.num_cols
+            && self.values().eq(other.values())
+    }
+}
+
+impl<T> std::fmt::Display for GridView<'_, T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for (_, x) in row {
+                write!(f, "{x}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<i32> {
+        [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]].into()
+    }
+
+    #[test]
+    fn view() {
+        let grid = grid();
+        let view = grid.view(&Box2D::new((1, 1), (3, 2)));
+        assert_eq!((view.num_rows(), view.num_cols()), (2, 3));
+        assert_eq!(view.offset(), Vec2D::new(1, 1));
+        assert_eq!(view[(0, 0)], 6);
+        assert_eq!(view.get((2, 1)), Some(&12));
+        assert_eq!(view.get((3, 1)), None);
+        assert_eq!(view.get((-1, 0)), None);
+        assert_eq!(view.to_grid(), Grid::from([[6, 7, 8], [10, 11, 12]]));
+        assert_eq!(view.col(1).map(|(_, v)| *v).collect_vec(), vec![7, 11]);
+        assert_eq!(view.to_string(), "678\n101112\n");
+
+        assert_eq!(grid.view_ranges(1..3, 1..4), view);
+        assert_eq!(view.view(&Box2D::new((1, 1), (2, 1))).to_grid(), Grid::from([[11, 12]]));
+
+        let pos = Vec2D::new(0, 0);
+        assert_eq!(view.orthogonal_neighbor_values(&pos).sorted().collect_vec(), vec![&7, &10]);
+        assert_eq!(view.all_neighbors(&pos).count(), 3);
+        assert_eq!(grid.as_view().to_grid(), grid);
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_out_of_bounds() {
+        grid().view(&Box2D::new((2, 2), (4, 3)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_inverted_area() {
+        grid().view(&Box2D::new((2, 2), (0, 0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_row_out_of_bounds() {
+        let grid = grid();
+        grid.view(&Box2D::new((0, 0), (1, 1))).row(2).count();
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_col_out_of_bounds() {
+        let grid = grid();
+        grid.view(&Box2D::new((0, 0), (1, 1))).col(3).count();
+    }
+
+    #[test]
+    fn empty_view() {
+        let grid = grid();
+        let view = grid.view_ranges(0..0, 1..3);
+        assert_eq!((view.num_rows(), view.num_cols()), (0, 2));
+        assert_eq!(view.iter().count(), 0);
+        assert_eq!(grid.view_ranges(2..4, 4..4).iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_windows() {
+        grid().windows(0).count();
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_tiles() {
+        grid().tiles(0).count();
+    }
+
+    #[test]
+    fn windows_and_tiles() {
+        let grid = grid();
+        let windows = grid.windows(3).collect_vec();
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[1].0, Vec2D::new(1, 0));
+        assert_eq!(windows[1].1.to_grid(), Grid::from([[2, 3, 4], [6, 7, 8], [10, 11, 12]]));
+        assert_eq!(grid.windows(5).count(), 0);
+
+        let tiles = grid.tiles(2).collect_vec();
+        assert_eq!(tiles.len(), 4);
+        assert_eq!(tiles[2].0, Vec2D::new(0, 1));
+        assert_eq!(tiles[2].1.to_grid(), Grid::from([[9, 10], [13, 14]]));
+        assert_eq!(tiles[3].1.iter().map(|(_, v)| v).sum::<i32>(), 11 + 12 + 15 + 16);
+    }
+}
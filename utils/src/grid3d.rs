@@ -0,0 +1,235 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    graphs::UnweightedGraph,
+    grid::Grid,
+    math::{Box3D, Neighborhood3D, Vec3D},
+};
+
+/// Axis perpendicular to a layer of a 3D grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    /// Position of the cell at `col`/`row` in layer `index`. Layers along X have columns along Y
+    /// and rows along Z, layers along Y have columns along X and rows along Z, and layers along Z
+    /// have columns along X and rows along Y.
+    pub(crate) fn point(self, index: i64, col: i64, row: i64) -> Vec3D {
+        match self {
+            Axis::X => Vec3D::new(index, col, row),
+            Axis::Y => Vec3D::new(col, index, row),
+            Axis::Z => Vec3D::new(col, row, index),
+        }
+    }
+
+    /// Inverse of [Axis::point], returns the layer index, column and row of `pos`
+    pub(crate) fn split(self, pos: Vec3D) -> (i64, i64, i64) {
+        match self {
+            Axis::X => (pos.x, pos.y, pos.z),
+            Axis::Y => (pos.y, pos.x, pos.z),
+            Axis::Z => (pos.z, pos.x, pos.y),
+        }
+    }
+}
+
+/// Dense 3D grid, stored in a single contiguous `Vec` with x varying fastest, then y, then z
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid3D<T> {
+    data: Vec<T>,
+    size: Vec3D,
+}
+
+impl<T, Pos> Index<Pos> for Grid3D<T>
+where
+    Pos: Into<Vec3D>,
+{
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap()
+    }
+}
+
+impl<T, Pos> IndexMut<Pos> for Grid3D<T>
+where
+    Pos: Into<Vec3D>,
+{
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos).unwrap()
+    }
+}
+
+impl<T: Clone> Grid3D<T> {
+    pub fn with_value(val: T, size: impl Into<Vec3D>) -> Self {
+        let size = size.into();
+        assert!(size.x >= 0 && size.y >= 0 && size.z >= 0);
+        Grid3D { data: vec![val; (size.x * size.y * size.z) as usize], size }
+    }
+
+    /// Copy of the 2D layer at `index` along `axis`, see [Axis] for how the axes are mapped
+    pub fn layer(&self, axis: Axis, index: i64) -> Grid<T> {
+        let (_, num_cols, num_rows) = axis.split(self.size);
+        (0..num_rows)
+            .map(|row| (0..num_cols).map(move |col| self[axis.point(index, col, row)].clone()))
+            .collect()
+    }
+}
+
+impl<T> Grid3D<T> {
+    /// Stacks 2D grids of the same size along the z axis
+    pub fn from_layers(layers: Vec<Grid<T>>) -> Self {
+        let (num_cols, num_rows) = layers.first().map_or((0, 0), |l| (l.num_cols(), l.num_rows()));
+        assert!(layers.iter().all(|l| (l.num_cols(), l.num_rows()) == (num_cols, num_rows)));
+        let size = Vec3D::from((num_cols, num_rows, layers.len()));
+        Grid3D { data: layers.into_iter().flat_map(Grid::into_inner).collect(), size }
+    }
+
+    pub fn size(&self) -> Vec3D {
+        self.size
+    }
+
+    pub fn bounding_box(&self) -> Box3D {
+        Box3D::new(Vec3D::zero(), self.size - (1, 1, 1))
+    }
+
+    pub fn contains(&self, pos: &Vec3D) -> bool {
+        self.bounding_box().contains(pos)
+    }
+
+    fn index_of(&self, pos: &Vec3D) -> usize {
+        ((pos.z * self.size.y + pos.y) * self.size.x + pos.x) as usize
+    }
+
+    pub fn get(&self, pos: impl Into<Vec3D>) -> Option<&T> {
+        let pos = pos.into();
+        self.contains(&pos).then(|| &self.data[self.index_of(&pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Vec3D>) -> Option<&mut T> {
+        let pos = pos.into();
+        if !self.contains(&pos) {
+            return None;
+        }
+        let index = self.index_of(&pos);
+        Some(&mut self.data[index])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec3D, &T)> + '_ {
+        let (size_x, size_y) = (self.size.x.max(1), self.size.y.max(1));
+        self.data.iter().enumerate().map(move |(i, value)| {
+            let i = i as i64;
+            (Vec3D::new(i % size_x, i / size_x % size_y, i / (size_x * size_y)), value)
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vec3D, &mut T)> + '_ {
+        let (size_x, size_y) = (self.size.x.max(1), self.size.y.max(1));
+        self.data.iter_mut().enumerate().map(move |(i, value)| {
+            let i = i as i64;
+            (Vec3D::new(i % size_x, i / size_x % size_y, i / (size_x * size_y)), value)
+        })
+    }
+
+    pub fn neighbors<'a, 'b: 'a>(
+        &'a self,
+        pos: &'b Vec3D,
+        neighborhood: Neighborhood3D,
+    ) -> impl Iterator<Item = Vec3D> + 'a {
+        pos.neighbors(neighborhood).filter(|pos| self.contains(pos))
+    }
+
+    pub fn neighbor_values<'a, 'b: 'a>(
+        &'a self,
+        pos: &'b Vec3D,
+        neighborhood: Neighborhood3D,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        pos.neighbors(neighborhood).filter_map(|pos| self.get(pos))
+    }
+
+    /// Graph with face neighbors, see [Grid3DGraph::neighborhood] to customize it
+    pub fn graph<P: Fn(&T) -> bool>(&self, passable: P) -> Grid3DGraph<'_, T, P> {
+        Grid3DGraph { grid: self, passable, neighborhood: Neighborhood3D::Faces }
+    }
+}
+
+/// Graph whose nodes are the positions of a [Grid3D], created by [Grid3D::graph].
+///
+/// Cells can only be entered if `passable` returns true for them.
+pub struct Grid3DGraph<'a, T, P> {
+    grid: &'a Grid3D<T>,
+    passable: P,
+    neighborhood: Neighborhood3D,
+}
+
+impl<T, P> Grid3DGraph<'_, T, P> {
+    pub fn neighborhood(self, neighborhood: Neighborhood3D) -> Self {
+        Grid3DGraph { neighborhood, ..self }
+    }
+}
+
+impl<T, P> UnweightedGraph for Grid3DGraph<'_, T, P>
+where
+    P: Fn(&T) -> bool,
+{
+    type Node = Vec3D;
+
+    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec3D) -> impl Iterator<Item = Vec3D> + 'a {
+        self.grid
+            .neighbors(node, self.neighborhood)
+            .filter(|neighbor| (self.passable)(&self.grid[*neighbor]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{graphs::floodfill, Input};
+
+    use super::*;
+
+    #[test]
+    fn layers() {
+        let mut grid = Grid3D::with_value('.', (3, 2, 4));
+        grid[(2, 1, 3)] = '#';
+        grid[(0, 1, 0)] = '#';
+        assert_eq!(grid.iter().filter(|(_, &c)| c == '#').count(), 2);
+        assert_eq!(grid.get((3, 0, 0)), None);
+        assert_eq!(grid.bounding_box(), Box3D::new((0, 0, 0), (2, 1, 3)));
+
+        assert_eq!(grid.layer(Axis::Z, 3), Input::from("...\n..#").char_grid());
+        assert_eq!(grid.layer(Axis::Y, 1), Input::from("#..\n...\n...\n..#").char_grid());
+        assert_eq!(grid.layer(Axis::X, 0), Input::from(".#\n..\n..\n..").char_grid());
+
+        let layers = (0..4).map(|z| grid.layer(Axis::Z, z)).collect();
+        assert_eq!(Grid3D::from_layers(layers), grid);
+    }
+
+    #[test]
+    fn empty_layer() {
+        let grid = Grid3D::with_value('.', (0, 3, 2));
+        let layer = grid.layer(Axis::Z, 0);
+        assert_eq!((layer.num_rows(), layer.num_cols()), (3, 0));
+        let layer = grid.layer(Axis::Y, 0);
+        assert_eq!((layer.num_rows(), layer.num_cols()), (2, 0));
+    }
+
+    #[test]
+    fn neighbors_and_floodfill() {
+        let mut grid = Grid3D::with_value(false, (3, 3, 3));
+        assert_eq!(grid.neighbors(&Vec3D::new(1, 1, 1), Neighborhood3D::All).count(), 26);
+        assert_eq!(grid.neighbors(&Vec3D::new(0, 0, 0), Neighborhood3D::Edges).count(), 6);
+        assert_eq!(grid.neighbor_values(&Vec3D::new(0, 0, 0), Neighborhood3D::Faces).count(), 3);
+
+        // A wall in the z = 1 layer with a single diagonal gap
+        for (pos, cell) in grid.iter_mut() {
+            *cell = pos.z == 1 && pos.xyz_tuple() != (2, 2, 1);
+        }
+        let graph = grid.graph(|&wall| !wall);
+        assert_eq!(floodfill(&graph, (0, 0, 0)).get(&Vec3D::new(0, 0, 2)), Some(&10));
+        let graph = graph.neighborhood(Neighborhood3D::All);
+        assert_eq!(floodfill(&graph, (0, 0, 0)).get(&Vec3D::new(0, 0, 2)), Some(&4));
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    graphs::UnweightedGraph,
+    math::{Hex, HexOrientation},
+    sparse_grid::SparseGrid,
+};
+
+/// Sparse grid of hexagons. As a graph, its nodes are all positions that contain a cell.
+#[derive(Debug, PartialEq, Eq, Clone, Default, derive_more::From)]
+pub struct HexGrid<T> {
+    data: HashMap<Hex, T>,
+}
+
+impl<T, H: Into<Hex>> FromIterator<(H, T)> for HexGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (H, T)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().map(|(pos, val)| (pos.into(), val)).collect::<HashMap<_, _>>())
+    }
+}
+
+impl<T> Deref for HexGrid<T> {
+    type Target = HashMap<Hex, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for HexGrid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T> HexGrid<T> {
+    pub fn new() -> Self {
+        HexGrid { data: HashMap::new() }
+    }
+
+    pub fn get(&self, pos: impl Into<Hex>) -> Option<&T> {
+        self.data.get(&pos.into())
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Hex>) -> Option<&mut T> {
+        self.data.get_mut(&pos.into())
+    }
+
+    pub fn neighbors<'a, 'b: 'a>(
+        &'a self,
+        pos: &'b Hex,
+    ) -> impl Iterator<Item = (Hex, &'a T)> + 'a {
+        pos.neighbors().filter_map(|pos| self.get(pos).map(|val| (pos, val)))
+    }
+
+    /// The cells in doubled coordinates (see [HexOrientation::to_doubled]), e.g. for rendering
+    /// with [SparseGrid::renderer]
+    pub fn to_sparse_grid(&self, orientation: HexOrientation) -> SparseGrid<&T> {
+        SparseGrid::from(
+            self.data
+                .iter()
+                .map(|(&pos, val)| (orientation.to_doubled(pos), val))
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+}
+
+impl<T> UnweightedGraph for HexGrid<T> {
+    type Node = Hex;
+
+    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Hex) -> impl Iterator<Item = Hex> + 'a {
+        node.neighbors().filter(|pos| self.contains_key(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphs::bfs;
+
+    use super::*;
+
+    #[test]
+    fn graph() {
+        let ring: HexGrid<_> = Hex::directions().into_iter().map(|dir| (dir, ())).collect();
+        assert_eq!(bfs(&ring, Hex::new(1, 0), Hex::new(-1, 0)).distance, Some(3));
+
+        let mut disk: HexGrid<_> = ring.keys().map(|&pos| (pos, ())).collect();
+        disk.insert(Hex::zero(), ());
+        assert_eq!(bfs(&disk, Hex::new(1, 0), Hex::new(-1, 0)).distance, Some(2));
+        assert_eq!(disk.neighbors(&Hex::new(1, 0)).count(), 3);
+    }
+
+    #[test]
+    fn render() {
+        let grid: HexGrid<_> = [((0, 0), 'a'), ((1, 0), 'b'), ((0, 1), 'c')].into_iter().collect();
+        assert_eq!(
+            grid.to_sparse_grid(HexOrientation::PointyTop).renderer().empty(' ').to_string(),
+            "a b\n c \n"
+        );
+        assert_eq!(
+            grid.to_sparse_grid(HexOrientation::FlatTop).renderer().empty(' ').to_string(),
+            "a \n b\nc \n"
+        );
+    }
+}
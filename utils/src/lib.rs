@@ -7,8 +7,19 @@ pub use input::*;
 
 pub mod graphs;
 
+pub mod automaton;
+pub mod cycle;
+
 pub mod grid;
+pub mod bit_grid;
+pub mod grid3d;
+pub mod hex_grid;
 pub mod sparse_grid;
+pub mod sparse_grid3d;
+
+pub mod ocr;
+pub mod recorder;
+pub mod render;
 
 mod regex_helper;
 pub use regex_helper::*;
@@ -4,3 +4,13 @@ mod box2d;
 pub use box2d::*;
 mod line1d;
 pub use line1d::*;
+mod direction;
+pub use direction::*;
+mod pose;
+pub use pose::*;
+mod vec3d;
+pub use vec3d::*;
+mod box3d;
+pub use box3d::*;
+mod hex;
+pub use hex::*;
@@ -0,0 +1,78 @@
+use crate::math::Vec3D;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Box3D {
+    pub lower: Vec3D,
+    pub upper: Vec3D,
+}
+
+impl FromIterator<Vec3D> for Box3D {
+    fn from_iter<I: IntoIterator<Item = Vec3D>>(iter: I) -> Self {
+        let mut box3d = Self::new((i64::MAX, i64::MAX, i64::MAX), (i64::MIN, i64::MIN, i64::MIN));
+        for point in iter {
+            box3d.extend(point);
+        }
+        box3d
+    }
+}
+
+impl Box3D {
+    pub fn new(lower: impl Into<Vec3D>, upper: impl Into<Vec3D>) -> Self {
+        Self { lower: lower.into(), upper: upper.into() }
+    }
+
+    pub fn extend_border(&self, amount: i64) -> Self {
+        Self::new(self.lower - (amount, amount, amount), self.upper + (amount, amount, amount))
+    }
+
+    pub fn contains(&self, point: &Vec3D) -> bool {
+        (self.lower.x..=self.upper.x).contains(&point.x)
+            && (self.lower.y..=self.upper.y).contains(&point.y)
+            && (self.lower.z..=self.upper.z).contains(&point.z)
+    }
+
+    pub fn extend(&mut self, point: impl Into<Vec3D>) {
+        let point = point.into();
+        self.lower = Vec3D::new(
+            self.lower.x.min(point.x),
+            self.lower.y.min(point.y),
+            self.lower.z.min(point.z),
+        );
+        self.upper = Vec3D::new(
+            self.upper.x.max(point.x),
+            self.upper.y.max(point.y),
+            self.upper.z.max(point.z),
+        );
+    }
+
+    pub fn num_points(&self) -> usize {
+        let size = self.upper - self.lower + (1, 1, 1);
+        (size.x * size.y * size.z) as usize
+    }
+
+    pub fn points_inside(&self) -> impl Iterator<Item = Vec3D> + '_ {
+        (self.lower.z..=self.upper.z).flat_map(move |z| {
+            (self.lower.y..=self.upper.y)
+                .flat_map(move |y| (self.lower.x..=self.upper.x).map(move |x| Vec3D::new(x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_and_extend() {
+        let mut box3d: Box3D = [Vec3D::new(0, 0, 0), Vec3D::new(2, 1, 3)].into_iter().collect();
+        assert_eq!(box3d, Box3D::new((0, 0, 0), (2, 1, 3)));
+        assert_eq!(box3d.num_points(), 24);
+        assert_eq!(box3d.points_inside().count(), 24);
+        assert!(box3d.contains(&Vec3D::new(2, 1, 3)));
+        assert!(!box3d.contains(&Vec3D::new(2, 2, 3)));
+
+        box3d.extend((-1, 0, 0));
+        assert_eq!(box3d, Box3D::new((-1, 0, 0), (2, 1, 3)));
+        assert_eq!(box3d.extend_border(1), Box3D::new((-2, -1, -1), (3, 2, 4)));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::math::Vec2D;
+
+/// One of the four orthogonal directions, in screen coordinates (y grows downwards)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions in clockwise order, starting with [Direction::Up]
+    pub fn all() -> [Direction; 4] {
+        [Direction::Up, Direction::Right, Direction::Down, Direction::Left]
+    }
+
+    pub fn turned_right(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turned_left(&self) -> Self {
+        self.opposite().turned_right()
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turned_right().turned_right()
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        !self.is_horizontal()
+    }
+}
+
+impl From<Direction> for Vec2D {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Vec2D::new(0, -1),
+            Direction::Right => Vec2D::new(1, 0),
+            Direction::Down => Vec2D::new(0, 1),
+            Direction::Left => Vec2D::new(-1, 0),
+        }
+    }
+}
+
+/// Accepts arrows (`^>v<`), `UDLR` and compass directions (`NESW`)
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            'v' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            _ => Err(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turn() {
+        assert_eq!(Direction::Up.turned_right(), Direction::Right);
+        assert_eq!(Direction::Up.turned_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert!(Direction::Left.is_horizontal());
+        assert!(Direction::Down.is_vertical());
+
+        for dir in Direction::all() {
+            assert_eq!(Vec2D::from(dir.turned_right()), Vec2D::from(dir).rotated_right());
+            assert_eq!(Vec2D::from(dir.turned_left()), Vec2D::from(dir).rotated_left());
+        }
+    }
+
+    #[test]
+    fn from_char() {
+        assert_eq!(Direction::try_from('^'), Ok(Direction::Up));
+        assert_eq!(Direction::try_from('>'), Ok(Direction::Right));
+        assert_eq!(Direction::try_from('S'), Ok(Direction::Down));
+        assert_eq!(Direction::try_from('L'), Ok(Direction::Left));
+        assert_eq!(Direction::try_from('x'), Err('x'));
+        assert_eq!(Vec2D::new(1, 1) + Direction::Up, (1, 0));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::math::Vec2D;
+
+/// Position on a hexagonal grid in axial coordinates. The third cube coordinate is `s = -q - r`.
+///
+/// Directions like "ne" depend on whether the hexagons have a pointy or a flat top, see
+/// [HexOrientation] for how they map to axial coordinates.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    derive_more::Add,
+    derive_more::AddAssign,
+    derive_more::Sub,
+    derive_more::SubAssign,
+    derive_more::Neg,
+)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    pub fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates must sum to zero");
+        Self::new(q, r)
+    }
+
+    pub fn zero() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    /// The six unit steps in clockwise order (in screen coordinates), starting with +q
+    pub fn directions() -> [Hex; 6] {
+        [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)].map(|(q, r)| Hex::new(q, r))
+    }
+
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex> + '_ {
+        Self::directions().into_iter().map(move |dir| *self + dir)
+    }
+
+    /// Number of steps to the origin
+    pub fn hex_dist(&self) -> usize {
+        ((self.q.abs() + self.r.abs() + self.s().abs()) / 2) as usize
+    }
+
+    pub fn distance(&self, other: &Hex) -> usize {
+        (*self - *other).hex_dist()
+    }
+
+    /// Rotated by 60 degrees clockwise around the origin
+    pub fn rotated_right(&self) -> Self {
+        let (q, r, s) = self.cube();
+        Self::from_cube(-r, -s, -q)
+    }
+
+    /// Rotated by 60 degrees counterclockwise around the origin
+    pub fn rotated_left(&self) -> Self {
+        let (q, r, s) = self.cube();
+        Self::from_cube(-s, -q, -r)
+    }
+}
+
+impl<T, U> From<(T, U)> for Hex
+where
+    T: num::ToPrimitive,
+    U: num::ToPrimitive,
+{
+    fn from((q, r): (T, U)) -> Self {
+        Self::new(q.to_i64().unwrap(), r.to_i64().unwrap())
+    }
+}
+
+impl<T> std::ops::Mul<T> for Hex
+where
+    T: num::ToPrimitive + num::Integer,
+{
+    type Output = Hex;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let rhs = rhs.to_i64().unwrap();
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+/// How hexagons are laid out on screen.
+///
+/// With pointy tops, +q points east and +r southeast, so the directions are e, se, sw, w, nw
+/// and ne. With flat tops, +q points southeast and +r south, so the directions are se, s, sw, nw, n
+/// and ne.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexOrientation {
+    PointyTop,
+    FlatTop,
+}
+
+impl HexOrientation {
+    /// Names of [Hex::directions]
+    pub fn direction_names(self) -> [&'static str; 6] {
+        match self {
+            HexOrientation::PointyTop => ["e", "se", "sw", "w", "nw", "ne"],
+            HexOrientation::FlatTop => ["se", "s", "sw", "nw", "n", "ne"],
+        }
+    }
+
+    /// Parses a direction like "nw" (case-insensitive)
+    pub fn direction(self, name: &str) -> Option<Hex> {
+        self.direction_names()
+            .into_iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(name))
+            .map(|i| Hex::directions()[i])
+    }
+
+    /// Parses a sequence of directions, either separated by commas or whitespace ("ne,s,s") or
+    /// not separated at all ("nwwswee"). Returns the unparseable rest of `path` on failure.
+    pub fn parse_path(self, path: &str) -> Result<Vec<Hex>, String> {
+        let mut steps = Vec::new();
+        let mut rest = path.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        while !rest.is_empty() {
+            let (dir, len) = [2, 1]
+                .into_iter()
+                .filter_map(|len| Some((self.direction(rest.get(..len)?)?, len)))
+                .next()
+                .ok_or_else(|| rest.to_string())?;
+            steps.push(dir);
+            rest = rest[len..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        }
+        Ok(steps)
+    }
+
+    /// Offset coordinates (column, row), where odd rows (pointy tops) or odd columns (flat tops)
+    /// are shifted by half a hexagon
+    pub fn to_offset(self, hex: Hex) -> Vec2D {
+        match self {
+            HexOrientation::PointyTop => Vec2D::new(hex.q + (hex.r - (hex.r & 1)) / 2, hex.r),
+            HexOrientation::FlatTop => Vec2D::new(hex.q, hex.r + (hex.q - (hex.q & 1)) / 2),
+        }
+    }
+
+    pub fn from_offset(self, pos: Vec2D) -> Hex {
+        match self {
+            HexOrientation::PointyTop => Hex::new(pos.x - (pos.y - (pos.y & 1)) / 2, pos.y),
+            HexOrientation::FlatTop => Hex::new(pos.x, pos.y - (pos.x - (pos.x & 1)) / 2),
+        }
+    }
+
+    /// Doubled coordinates, where neighbors in the same row (pointy tops) or column (flat tops)
+    /// are two cells apart. Suitable for rendering as text, since every hexagon gets its own
+    /// character cell.
+    pub fn to_doubled(self, hex: Hex) -> Vec2D {
+        match self {
+            HexOrientation::PointyTop => Vec2D::new(2 * hex.q + hex.r, hex.r),
+            HexOrientation::FlatTop => Vec2D::new(hex.q, 2 * hex.r + hex.q),
+        }
+    }
+
+    pub fn from_doubled(self, pos: Vec2D) -> Hex {
+        match self {
+            HexOrientation::PointyTop => Hex::new((pos.x - pos.y) / 2, pos.y),
+            HexOrientation::FlatTop => Hex::new(pos.x, (pos.y - pos.x) / 2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        let flat = HexOrientation::FlatTop;
+        let walk =
+            |path| flat.parse_path(path).unwrap().into_iter().fold(Hex::zero(), |a, b| a + b);
+        assert_eq!(walk("ne,ne,ne").hex_dist(), 3);
+        assert_eq!(walk("ne,ne,sw,sw").hex_dist(), 0);
+        assert_eq!(walk("ne,ne,s,s").hex_dist(), 2);
+        assert_eq!(walk("se,sw,se,sw,sw").hex_dist(), 3);
+
+        let pointy = HexOrientation::PointyTop;
+        assert_eq!(
+            pointy.parse_path("esew"),
+            Ok(vec![Hex::new(1, 0), Hex::new(0, 1), Hex::new(-1, 0)])
+        );
+        assert_eq!(
+            pointy.parse_path("nwwswee").unwrap().into_iter().fold(Hex::zero(), |a, b| a + b),
+            Hex::zero()
+        );
+        assert_eq!(pointy.parse_path("nw s"), Err("s".to_string()));
+    }
+
+    #[test]
+    fn geometry() {
+        let hex = Hex::new(2, -1);
+        assert_eq!(hex.neighbors().count(), 6);
+        assert!(hex.neighbors().all(|n| n.distance(&hex) == 1));
+        assert_eq!(Hex::new(3, -1).distance(&Hex::new(-1, 2)), 4);
+
+        assert_eq!(hex.rotated_right(), Hex::new(1, 1));
+        assert_eq!(hex.rotated_left(), Hex::new(1, -2));
+        assert_eq!((0..6).fold(hex, |hex, _| hex.rotated_right()), hex);
+        let dirs = Hex::directions();
+        assert!((0..6).all(|i| dirs[i].rotated_right() == dirs[(i + 1) % 6]));
+        assert_eq!(hex * 2 - hex, hex);
+    }
+
+    #[test]
+    fn layouts() {
+        for orientation in [HexOrientation::PointyTop, HexOrientation::FlatTop] {
+            for q in -3..=3 {
+                for r in -3..=3 {
+                    let hex = Hex::new(q, r);
+                    assert_eq!(orientation.from_offset(orientation.to_offset(hex)), hex);
+                    assert_eq!(orientation.from_doubled(orientation.to_doubled(hex)), hex);
+                }
+            }
+        }
+        assert_eq!(HexOrientation::PointyTop.to_offset(Hex::new(-1, 3)), Vec2D::new(0, 3));
+        assert_eq!(HexOrientation::FlatTop.to_offset(Hex::new(3, -1)), Vec2D::new(3, 0));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::math::{Direction, Vec2D};
+
+/// A position together with the direction it is facing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pose {
+    pub pos: Vec2D,
+    pub dir: Direction,
+}
+
+impl Pose {
+    pub fn new(pos: impl Into<Vec2D>, dir: Direction) -> Self {
+        Self { pos: pos.into(), dir }
+    }
+
+    /// One step forward
+    pub fn advanced(&self) -> Self {
+        self.advanced_by(1)
+    }
+
+    pub fn advanced_by(&self, steps: i64) -> Self {
+        Self::new(self.pos + Vec2D::from(self.dir) * steps, self.dir)
+    }
+
+    pub fn turned_left(&self) -> Self {
+        Self::new(self.pos, self.dir.turned_left())
+    }
+
+    pub fn turned_right(&self) -> Self {
+        Self::new(self.pos, self.dir.turned_right())
+    }
+
+    pub fn turned_around(&self) -> Self {
+        Self::new(self.pos, self.dir.opposite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walk() {
+        let pose = Pose::new((1, 1), Direction::Up);
+        assert_eq!(pose.advanced(), Pose::new((1, 0), Direction::Up));
+        assert_eq!(pose.advanced_by(3), Pose::new((1, -2), Direction::Up));
+        assert_eq!(pose.turned_right().advanced(), Pose::new((2, 1), Direction::Right));
+        assert_eq!(pose.turned_left().advanced(), Pose::new((0, 1), Direction::Left));
+        assert_eq!(pose.turned_around().advanced(), Pose::new((1, 2), Direction::Down));
+    }
+}
@@ -9,6 +9,17 @@ pub struct Vec2D {
     pub y: i64,
 }
 
+/// Which cells count as neighbors of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// The 4 cells sharing an edge
+    Orthogonal,
+    /// The 4 cells sharing only a corner
+    Diagonal,
+    /// All 8 surrounding cells
+    All,
+}
+
 impl<T> PartialEq<T> for Vec2D
 where
     T: Into<Vec2D> + Copy,
@@ -88,6 +99,17 @@ impl Vec2D {
     pub fn all_neighbors(&self) -> impl Iterator<Item = Vec2D> + '_ {
         self.diagonal_neighbors().chain(self.orthogonal_neighbors())
     }
+
+    pub fn neighbors(&self, neighborhood: Neighborhood) -> impl Iterator<Item = Vec2D> + '_ {
+        let (orthogonal, diagonal) = match neighborhood {
+            Neighborhood::Orthogonal => (true, false),
+            Neighborhood::Diagonal => (false, true),
+            Neighborhood::All => (true, true),
+        };
+        self.orthogonal_neighbors()
+            .filter(move |_| orthogonal)
+            .chain(self.diagonal_neighbors().filter(move |_| diagonal))
+    }
 }
 
 /// This would of course be horrible in productions, but it make the Advent of Code solutions much
@@ -298,5 +320,19 @@ mod tests {
             ]),
             Vec2D::new(1, 2).all_neighbors().collect(),
         );
+
+        let pos = Vec2D::new(1, 2);
+        assert_eq!(
+            pos.neighbors(Neighborhood::Orthogonal).collect::<HashSet<_>>(),
+            pos.orthogonal_neighbors().collect()
+        );
+        assert_eq!(
+            pos.neighbors(Neighborhood::Diagonal).collect::<HashSet<_>>(),
+            pos.diagonal_neighbors().collect()
+        );
+        assert_eq!(
+            pos.neighbors(Neighborhood::All).collect::<HashSet<_>>(),
+            pos.all_neighbors().collect()
+        );
     }
 }
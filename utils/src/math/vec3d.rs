@@ -0,0 +1,162 @@
+use parse_display::FromStr;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, FromStr, PartialOrd, Ord, derive_more::Display)]
+#[display("({x}, {y}, {z})")]
+// Parses 1,2,3 or [1, 2, 3] or (1, 2, 3) or {1, 2, 3}
+#[from_str(regex = r"[\[\(\{]?(?<x>-?\d+),\s*(?<y>-?\d+),\s*(?<z>-?\d+)[\]\)\}]?")]
+pub struct Vec3D {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// Which cells count as neighbors of a cell in 3D
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood3D {
+    /// The 6 cells sharing a face
+    Faces,
+    /// The 18 cells sharing a face or an edge
+    Edges,
+    /// All 26 surrounding cells
+    All,
+}
+
+impl<T> PartialEq<T> for Vec3D
+where
+    T: Into<Vec3D> + Copy,
+{
+    fn eq(&self, other: &T) -> bool {
+        let other: Vec3D = (*other).into();
+        self.x == other.x && self.y == other.y && self.z == other.z
+    }
+}
+
+impl Vec3D {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn xyz_tuple(&self) -> (i64, i64, i64) {
+        (self.x, self.y, self.z)
+    }
+
+    pub fn zero() -> Self {
+        Self::new(0, 0, 0)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    pub fn manhattan_dist(&self) -> usize {
+        (self.x.abs() + self.y.abs() + self.z.abs()) as usize
+    }
+
+    pub fn neighbors(&self, neighborhood: Neighborhood3D) -> impl Iterator<Item = Vec3D> + '_ {
+        let max_nonzero = match neighborhood {
+            Neighborhood3D::Faces => 1,
+            Neighborhood3D::Edges => 2,
+            Neighborhood3D::All => 3,
+        };
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Vec3D::new(x, y, z))))
+            .filter(move |dir| (1..=max_nonzero).contains(&dir.abs().manhattan_dist()))
+            .map(move |dir| *self + dir)
+    }
+}
+
+impl<T, U, V> From<(T, U, V)> for Vec3D
+where
+    T: num::ToPrimitive,
+    U: num::ToPrimitive,
+    V: num::ToPrimitive,
+{
+    fn from((x, y, z): (T, U, V)) -> Self {
+        Self::new(x.to_i64().unwrap(), y.to_i64().unwrap(), z.to_i64().unwrap())
+    }
+}
+
+impl<T> std::ops::Mul<T> for Vec3D
+where
+    T: num::ToPrimitive + num::Integer,
+{
+    type Output = Vec3D;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let rhs = rhs.to_i64().unwrap();
+        Vec3D::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T> std::ops::Add<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    type Output = Vec3D;
+
+    fn add(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+        Vec3D::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T> std::ops::AddAssign<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> std::ops::Sub<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    type Output = Vec3D;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+        Vec3D::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T> std::ops::SubAssign<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetics() {
+        let mut vec = Vec3D::new(1, 2, 3) + (1, -1, 0);
+        assert_eq!(vec, (2, 1, 3));
+        vec -= (2, 1, 3);
+        assert_eq!(vec, Vec3D::zero());
+        assert_eq!(Vec3D::new(1, -2, 3) * 2, (2, -4, 6));
+        assert_eq!(Vec3D::new(1, -2, 3).manhattan_dist(), 6);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("1,2,3".parse(), Ok(Vec3D::new(1, 2, 3)));
+        assert_eq!("(1, -2, 3)".parse(), Ok(Vec3D::new(1, -2, 3)));
+        assert_eq!("[1,2,-3]".parse(), Ok(Vec3D::new(1, 2, -3)));
+    }
+
+    #[test]
+    fn neighbors() {
+        let pos = Vec3D::new(1, 1, 1);
+        assert_eq!(pos.neighbors(Neighborhood3D::Faces).count(), 6);
+        assert_eq!(pos.neighbors(Neighborhood3D::Edges).count(), 18);
+        assert_eq!(pos.neighbors(Neighborhood3D::All).count(), 26);
+        assert!(pos.neighbors(Neighborhood3D::Faces).all(|n| (n - pos).manhattan_dist() == 1));
+    }
+}
@@ -0,0 +1,225 @@
+use std::{collections::HashMap, fmt, hash::Hash, sync::LazyLock};
+
+use itertools::Itertools;
+
+use crate::{
+    grid::Grid,
+    math::{Box2D, Vec2D},
+    sparse_grid::SparseGrid,
+    Input,
+};
+
+/// The 4x6 letters (some are narrower or wider), as used in most puzzles
+const SMALL_GLYPHS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The 6x10 letters, as used in 2018 day 10
+const LARGE_GLYPHS: [(char, &str); 15] = [
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+/// Glyphs keyed by their picture with empty columns at the sides trimmed, and grouped by height
+static ALPHABETS: LazyLock<HashMap<usize, HashMap<String, char>>> = LazyLock::new(|| {
+    SMALL_GLYPHS
+        .into_iter()
+        .chain(LARGE_GLYPHS)
+        .map(|(letter, picture)| {
+            let (height, glyphs) =
+                split_glyphs(&Input::from(picture).char_grid().map(|&c| c == '#'));
+            let [glyph] = glyphs.try_into().unwrap();
+            (height, glyph, letter)
+        })
+        .into_group_map_by(|&(height, _, _)| height)
+        .into_iter()
+        .map(|(height, glyphs)| {
+            (height, glyphs.into_iter().map(|(_, glyph, letter)| (glyph, letter)).collect())
+        })
+        .collect()
+});
+
+/// Returned by the OCR functions if some glyphs couldn't be recognized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcrError {
+    /// The recognized text, with `?` for each unrecognized glyph
+    pub text: String,
+    /// Pictures of all unrecognized glyphs
+    pub unrecognized: Vec<String>,
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "unrecognized glyphs in {:?}:", self.text)?;
+        for glyph in &self.unrecognized {
+            writeln!(f, "\n{glyph}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the letters drawn by `points`. Letters are separated by at least one empty column.
+pub fn ocr(points: impl IntoIterator<Item = impl Into<Vec2D>>) -> Result<String, OcrError> {
+    let points = points.into_iter().map(Into::into).collect_vec();
+    if points.is_empty() {
+        return Ok(String::new());
+    }
+    let bounds: Box2D = points.iter().copied().collect();
+    let size = bounds.upper - bounds.lower + (1, 1);
+    let mut lit = Grid::from_flat(vec![false; bounds.num_points()], size.x as usize);
+    for point in points {
+        lit[point - bounds.lower] = true;
+    }
+    recognize(&lit)
+}
+
+/// Splits the picture into glyphs at empty columns and looks them up in the alphabet matching the
+/// height of the picture
+fn recognize(lit: &Grid<bool>) -> Result<String, OcrError> {
+    let (height, glyphs) = split_glyphs(lit);
+    let alphabet = ALPHABETS.get(&height);
+
+    let mut text = String::new();
+    let mut unrecognized = Vec::new();
+    for glyph in glyphs {
+        match alphabet.and_then(|alphabet| alphabet.get(&glyph)) {
+            Some(&letter) => text.push(letter),
+            None => {
+                text.push('?');
+                unrecognized.push(glyph);
+            }
+        }
+    }
+
+    if unrecognized.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError { text, unrecognized })
+    }
+}
+
+/// Returns the height of the picture and the picture of each glyph, with '#' for lit cells
+fn split_glyphs(lit: &Grid<bool>) -> (usize, Vec<String>) {
+    let glyphs = (0..lit.num_cols())
+        .group_by(|&x| lit.col(x).any(|(_, &lit)| lit))
+        .into_iter()
+        .filter(|(nonempty, _)| *nonempty)
+        .map(|(_, cols)| {
+            let cols = cols.collect_vec();
+            (0..lit.num_rows())
+                .map(|y| {
+                    cols.iter().map(|&x| if lit[(x, y)] { '#' } else { '.' }).collect::<String>()
+                })
+                .join("\n")
+        })
+        .collect();
+    (lit.num_rows(), glyphs)
+}
+
+impl Grid<bool> {
+    /// Reads the letters drawn by the `true` cells
+    pub fn ocr(&self) -> Result<String, OcrError> {
+        ocr(self.iter().filter(|(_, &lit)| lit).map(|(pos, _)| pos))
+    }
+}
+
+impl Grid<char> {
+    /// Reads the letters drawn by the `#` (or `█`) cells
+    pub fn ocr(&self) -> Result<String, OcrError> {
+        ocr(self.iter().filter(|(_, &c)| c == '#' || c == '█').map(|(pos, _)| pos))
+    }
+}
+
+impl<T: Hash> SparseGrid<T> {
+    /// Reads the letters drawn by the positions of all cells
+    pub fn ocr(&self) -> Result<String, OcrError> {
+        ocr(self.keys().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        let grid = Input::from(
+            "
+.##..###..#..#.####.###...##..#.....##.
+#..#.#..#.#..#.#....#..#.#..#.#....#..#
+#..#.#..#.####.###..#..#.#....#....#..#
+####.###..#..#.#....###..#....#....#..#
+#..#.#.#..#..#.#....#....#..#.#....#..#
+#..#.#..#.#..#.####.#.....##..####..##.",
+        )
+        .char_grid();
+        assert_eq!(grid.ocr(), Ok("ARHEPCLO".to_string()));
+        assert_eq!(grid.map(|&c| c == '#').ocr(), Ok("ARHEPCLO".to_string()));
+    }
+
+    #[test]
+    fn large() {
+        let picture = "
+#....#..######...####.
+#....#....#.....#....#
+#....#....#.....#.....
+#....#....#.....#.....
+######....#.....#.....
+#....#....#.....#..###
+#....#....#.....#....#
+#....#....#.....#....#
+#....#....#.....#...##
+#....#..######...###.#";
+        let grid: SparseGrid<_> = Input::from(picture)
+            .char_grid()
+            .iter()
+            .filter(|(_, &c)| c == '#')
+            .map(|(pos, _)| (pos + (100, -50), ()))
+            .collect();
+        // The large I is not part of the alphabet
+        assert_eq!(
+            grid.ocr(),
+            Err(OcrError {
+                text: "H?G".to_string(),
+                unrecognized: vec!["######\n..#...\n..#...\n..#...\n..#...\n..#...\n..#...\n..#...\n..#...\n######".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(ocr(Vec::<Vec2D>::new()), Ok(String::new()));
+    }
+}
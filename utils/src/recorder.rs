@@ -0,0 +1,177 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const CLEAR_TO_END: &str = "\x1b[J";
+
+/// Captures rendered frames of a simulation, to be replayed in the terminal or dumped to a file
+/// afterwards.
+///
+/// A disabled recorder never calls the frame closure passed to [Recorder::record], so leaving the
+/// recording calls in place costs next to nothing in normal runs.
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    enabled: bool,
+    frames: Vec<String>,
+}
+
+impl Recorder {
+    pub fn new(enabled: bool) -> Self {
+        Recorder { enabled, frames: Vec::new() }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(false)
+    }
+
+    /// Enabled if the environment variable `var` is set, e.g. `AOC_RECORD=1 cargo run`
+    pub fn from_env(var: &str) -> Self {
+        Self::new(std::env::var_os(var).is_some())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Record the frame produced by `frame`, e.g. `|| grid.renderer().highlight([robot], '@')`
+    pub fn record<D: Display>(&mut self, frame: impl FnOnce() -> D) {
+        if self.enabled {
+            self.frames.push(frame().to_string());
+        }
+    }
+
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    /// Play back all frames on stdout at `fps` frames per second
+    pub fn replay(&self, fps: f64) -> io::Result<()> {
+        self.replay_to(&mut io::stdout().lock(), fps)
+    }
+
+    /// Play back all frames at `fps` frames per second, redrawing each frame in place. The cursor
+    /// is shown again even if writing fails partway.
+    pub fn replay_to(&self, out: &mut impl Write, fps: f64) -> io::Result<()> {
+        if fps.is_nan() || fps <= 0.0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("fps must be positive, got {fps}"),
+            ));
+        }
+        let delay = Duration::from_secs_f64(1.0 / fps);
+
+        let mut play = || -> io::Result<()> {
+            write!(out, "{HIDE_CURSOR}{CLEAR_SCREEN}")?;
+            for (i, frame) in self.frames.iter().enumerate() {
+                write!(out, "{CURSOR_HOME}{frame}{CLEAR_TO_END}")?;
+                writeln!(out, "frame {}/{}", i + 1, self.frames.len())?;
+                out.flush()?;
+                thread::sleep(delay);
+            }
+            Ok(())
+        };
+        let played = play();
+
+        let restored = write!(out, "{SHOW_CURSOR}").and_then(|_| out.flush());
+        played.and(restored)
+    }
+
+    /// Write all frames to `path`, each preceded by a separator line
+    pub fn dump(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        self.dump_to(&mut file)?;
+        file.flush()
+    }
+
+    pub fn dump_to(&self, out: &mut impl Write) -> io::Result<()> {
+        for (i, frame) in self.frames.iter().enumerate() {
+            writeln!(out, "--- frame {} ---", i + 1)?;
+            write!(out, "{frame}")?;
+            if !frame.ends_with('\n') {
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+
+    use super::*;
+
+    #[test]
+    fn record() {
+        let mut grid: Grid<_> = [['.', '.'], ['.', '.']].into();
+        let mut recorder = Recorder::new(true);
+        for pos in [(0, 0), (1, 1)] {
+            grid[pos] = '#';
+            recorder.record(|| grid.renderer());
+        }
+        assert_eq!(recorder.frames(), ["#.\n..\n", "#.\n.#\n"]);
+
+        let mut dump = Vec::new();
+        recorder.dump_to(&mut dump).unwrap();
+        assert_eq!(
+            String::from_utf8(dump).unwrap(),
+            "--- frame 1 ---\n#.\n..\n--- frame 2 ---\n#.\n.#\n"
+        );
+
+        let mut replay = Vec::new();
+        recorder.replay_to(&mut replay, 1000.0).unwrap();
+        let replay = String::from_utf8(replay).unwrap();
+        assert!(replay.contains("\x1b[H#.\n.#\n\x1b[Jframe 2/2\n"));
+        assert!(replay.ends_with(SHOW_CURSOR));
+    }
+
+    /// Accepts all writes, but fails the first flush
+    struct FailingFlush {
+        written: Vec<u8>,
+        failed: bool,
+    }
+
+    impl Write for FailingFlush {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            if self.failed {
+                return Ok(());
+            }
+            self.failed = true;
+            Err(io::Error::other("flush failed"))
+        }
+    }
+
+    #[test]
+    fn replay_errors() {
+        let mut recorder = Recorder::new(true);
+        recorder.record(|| "#\n");
+
+        let error = recorder.replay_to(&mut Vec::new(), 0.0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(recorder.replay_to(&mut Vec::new(), f64::NAN).is_err());
+
+        let mut out = FailingFlush { written: Vec::new(), failed: false };
+        assert!(recorder.replay_to(&mut out, 1000.0).is_err());
+        assert!(out.written.ends_with(SHOW_CURSOR.as_bytes()));
+    }
+
+    #[test]
+    fn disabled() {
+        let mut recorder = Recorder::disabled();
+        recorder.record(|| -> String { panic!("rendered a frame while disabled") });
+        assert!(recorder.frames().is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Write},
+};
+
+use crate::{
+    grid::Grid,
+    math::{Box2D, Vec2D},
+    sparse_grid::SparseGrid,
+};
+
+/// ANSI terminal colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        30 + self as u8
+    }
+}
+
+enum Cells<'a, T> {
+    Dense(&'a Grid<T>),
+    Sparse(&'a SparseGrid<T>),
+}
+
+impl<T> Cells<'_, T> {
+    fn get(&self, pos: &Vec2D) -> Option<&T> {
+        match self {
+            Cells::Dense(grid) => grid.get(*pos),
+            Cells::Sparse(grid) => (**grid).get(pos),
+        }
+    }
+}
+
+type CellFn<'a, T, R> = Box<dyn Fn(&T) -> R + 'a>;
+
+struct Highlight {
+    positions: HashSet<Vec2D>,
+    marker: char,
+    color: Option<Color>,
+}
+
+/// Builder for rendering a [Grid] or [SparseGrid] as text, created by [Grid::renderer] or
+/// [SparseGrid::renderer]. The result is available through [Display].
+///
+/// Highlights are drawn on top of the cells, later highlights on top of earlier ones.
+/// Colors are emitted as ANSI escape codes unless disabled with [Renderer::no_color].
+pub struct Renderer<'a, T> {
+    cells: Cells<'a, T>,
+    bounds: Box2D,
+    cell: CellFn<'a, T, String>,
+    color: CellFn<'a, T, Option<Color>>,
+    empty: char,
+    highlights: Vec<Highlight>,
+    colored: bool,
+}
+
+impl<T: Display> Grid<T> {
+    pub fn renderer(&self) -> Renderer<'_, T> {
+        self.renderer_with(|cell| cell.to_string())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Renders each cell with `cell` instead of its [Display] impl
+    pub fn renderer_with<'a, S: Display>(&'a self, cell: impl Fn(&T) -> S + 'a) -> Renderer<'a, T> {
+        let bounds = Box2D::new((0, 0), (self.num_cols() as i64 - 1, self.num_rows() as i64 - 1));
+        Renderer::new(Cells::Dense(self), bounds, cell)
+    }
+}
+
+impl<T: Display> SparseGrid<T> {
+    pub fn renderer(&self) -> Renderer<'_, T> {
+        self.renderer_with(|cell| cell.to_string())
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// Renders each cell with `cell` instead of its [Display] impl. By default the bounding box
+    /// of all cells is rendered, with `.` for missing cells.
+    pub fn renderer_with<'a, S: Display>(&'a self, cell: impl Fn(&T) -> S + 'a) -> Renderer<'a, T> {
+        let bounds = self.keys().copied().collect();
+        Renderer::new(Cells::Sparse(self), bounds, cell)
+    }
+}
+
+impl<'a, T> Renderer<'a, T> {
+    fn new<S: Display>(cells: Cells<'a, T>, bounds: Box2D, cell: impl Fn(&T) -> S + 'a) -> Self {
+        Renderer {
+            cells,
+            bounds,
+            cell: Box::new(move |value| cell(value).to_string()),
+            color: Box::new(|_| None),
+            empty: '.',
+            highlights: Vec::new(),
+            colored: true,
+        }
+    }
+
+    /// Draw `marker` at all of `positions`
+    pub fn highlight<V: Into<Vec2D>>(
+        self,
+        positions: impl IntoIterator<Item = V>,
+        marker: char,
+    ) -> Self {
+        self.add_highlight(positions, marker, None)
+    }
+
+    /// Draw `marker` in `color` at all of `positions`
+    pub fn highlight_colored<V: Into<Vec2D>>(
+        self,
+        positions: impl IntoIterator<Item = V>,
+        marker: char,
+        color: Color,
+    ) -> Self {
+        self.add_highlight(positions, marker, Some(color))
+    }
+
+    fn add_highlight<V: Into<Vec2D>>(
+        mut self,
+        positions: impl IntoIterator<Item = V>,
+        marker: char,
+        color: Option<Color>,
+    ) -> Self {
+        let positions = positions.into_iter().map(Into::into).collect();
+        self.highlights.push(Highlight { positions, marker, color });
+        self
+    }
+
+    /// Color each cell depending on its value
+    pub fn color(mut self, color: impl Fn(&T) -> Option<Color> + 'a) -> Self {
+        self.color = Box::new(color);
+        self
+    }
+
+    /// Character for positions without a cell
+    pub fn empty(self, empty: char) -> Self {
+        Renderer { empty, ..self }
+    }
+
+    /// Only render the given area
+    pub fn bounds(self, bounds: Box2D) -> Self {
+        Renderer { bounds, ..self }
+    }
+
+    /// Don't emit any ANSI escape codes
+    pub fn no_color(self) -> Self {
+        Renderer { colored: false, ..self }
+    }
+
+    fn write_cell(&self, f: &mut fmt::Formatter<'_>, pos: &Vec2D) -> fmt::Result {
+        let value = self.cells.get(pos);
+        let (text, color) = match self
+            .highlights
+            .iter()
+            .rev()
+            .find(|highlight| highlight.positions.contains(pos))
+        {
+            Some(highlight) => (highlight.marker.to_string(), highlight.color),
+            None => match value {
+                Some(value) => ((self.cell)(value), (self.color)(value)),
+                None => (self.empty.to_string(), None),
+            },
+        };
+
+        match color.filter(|_| self.colored) {
+            Some(color) => write!(f, "\x1b[{}m{text}\x1b[0m", color.code()),
+            None => f.write_str(&text),
+        }
+    }
+}
+
+impl<T> Display for Renderer<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in self.bounds.lower.y..=self.bounds.upper.y {
+            for x in self.bounds.lower.x..=self.bounds.upper.x {
+                self.write_cell(f, &Vec2D::new(x, y))?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    #[test]
+    fn highlight() {
+        let grid = Input::from("#..\n.#.\n..#").char_grid();
+        assert_eq!(grid.renderer().to_string(), grid.to_string());
+
+        let path = [(0, 1), (0, 2), (1, 2)];
+        assert_eq!(
+            grid.renderer().highlight(path, 'O').highlight([(1, 2)], 'X').to_string(),
+            "#..\nO#.\nOX#\n"
+        );
+        assert_eq!(
+            grid.renderer_with(|&c| if c == '#' { '█' } else { ' ' }).to_string(),
+            "█  \n █ \n  █\n"
+        );
+    }
+
+    #[test]
+    fn colors() {
+        let grid: Grid<_> = [[1, 2], [3, 4]].into();
+        let renderer = grid
+            .renderer()
+            .color(|&x| (x % 2 == 0).then_some(Color::Green))
+            .highlight_colored([(0, 1)], '*', Color::Red);
+        assert_eq!(renderer.to_string(), "1\x1b[32m2\x1b[0m\n\x1b[31m*\x1b[0m\x1b[32m4\x1b[0m\n");
+        assert_eq!(renderer.no_color().to_string(), "12\n*4\n");
+    }
+
+    #[test]
+    fn sparse() {
+        let grid: SparseGrid<_> = [((-1, 0), 'a'), ((1, 1), 'b')].into_iter().collect();
+        assert_eq!(grid.renderer().to_string(), "a..\n..b\n");
+        assert_eq!(grid.renderer().empty(' ').highlight([(0, 0)], '#').to_string(), "a# \n  b\n");
+        assert_eq!(grid.renderer().bounds(Box2D::new((0, 0), (2, 1))).to_string(), "...\n.b.\n");
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    hash::Hash,
+    hash::{DefaultHasher, Hash, Hasher},
     ops::{Deref, DerefMut},
 };
 
@@ -17,6 +17,24 @@ impl<T: Hash, V: Into<Vec2D>> FromIterator<(V, T)> for SparseGrid<T> {
     }
 }
 
+/// Order-independent, so that equal grids have equal hashes regardless of the iteration order of
+/// the underlying HashMap
+impl<T: Hash> Hash for SparseGrid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let combined = self
+            .data
+            .iter()
+            .map(|entry| {
+                let mut hasher = DefaultHasher::new();
+                entry.hash(&mut hasher);
+                hasher.finish()
+            })
+            .fold(0_u64, u64::wrapping_add);
+        self.data.len().hash(state);
+        combined.hash(state);
+    }
+}
+
 impl<T> Deref for SparseGrid<T> {
     type Target = HashMap<Vec2D, T>;
 
@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    graphs::UnweightedGraph,
+    grid::Grid,
+    grid3d::Axis,
+    math::{Box3D, Neighborhood3D, Vec3D},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, derive_more::From)]
+pub struct SparseGrid3D<T> {
+    data: HashMap<Vec3D, T>,
+}
+
+impl<T, V: Into<Vec3D>> FromIterator<(V, T)> for SparseGrid3D<T> {
+    fn from_iter<I: IntoIterator<Item = (V, T)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().map(|(pos, val)| (pos.into(), val)).collect::<HashMap<_, _>>())
+    }
+}
+
+impl<T> Deref for SparseGrid3D<T> {
+    type Target = HashMap<Vec3D, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for SparseGrid3D<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T> SparseGrid3D<T> {
+    pub fn new() -> Self {
+        SparseGrid3D { data: HashMap::new() }
+    }
+
+    pub fn get(&self, pos: impl Into<Vec3D>) -> Option<&T> {
+        self.data.get(&pos.into())
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Vec3D>) -> Option<&mut T> {
+        self.data.get_mut(&pos.into())
+    }
+
+    pub fn neighbors<'a, 'b: 'a>(
+        &'a self,
+        pos: &'b Vec3D,
+        neighborhood: Neighborhood3D,
+    ) -> impl Iterator<Item = (Vec3D, &'a T)> + 'a {
+        pos.neighbors(neighborhood).filter_map(|pos| self.get(pos).map(|val| (pos, val)))
+    }
+
+    /// Smallest box containing all cells, or None if the grid is empty
+    pub fn bounding_box(&self) -> Option<Box3D> {
+        (!self.data.is_empty()).then(|| self.data.keys().copied().collect())
+    }
+
+    /// The 2D layer at `index` along `axis`, spanning the bounding box of the whole grid (see
+    /// [Axis] for how the axes are mapped). The cell at (0, 0) of the layer corresponds to the lower
+    /// corner of the bounding box.
+    pub fn layer(&self, axis: Axis, index: i64) -> Grid<Option<&T>> {
+        let Some(bounds) = self.bounding_box() else {
+            return Grid::new(vec![]);
+        };
+        let (_, lower_col, lower_row) = axis.split(bounds.lower);
+        let (_, num_cols, num_rows) = axis.split(bounds.upper - bounds.lower + (1, 1, 1));
+        let cells = (0..num_rows)
+            .flat_map(|row| (0..num_cols).map(move |col| (col, row)))
+            .map(|(col, row)| self.get(axis.point(index, lower_col + col, lower_row + row)))
+            .collect();
+        Grid::from_flat(cells, num_cols as usize)
+    }
+
+    /// Graph over all positions inside `bounds`, with face neighbors. Positions can only be entered
+    /// if `passable` returns true for their cell (None if there is no cell).
+    pub fn graph<P: Fn(Option<&T>) -> bool>(
+        &self,
+        bounds: Box3D,
+        passable: P,
+    ) -> SparseGrid3DGraph<'_, T, P> {
+        SparseGrid3DGraph { grid: self, bounds, passable, neighborhood: Neighborhood3D::Faces }
+    }
+}
+
+/// Graph over the positions inside a box, created by [SparseGrid3D::graph]
+pub struct SparseGrid3DGraph<'a, T, P> {
+    grid: &'a SparseGrid3D<T>,
+    bounds: Box3D,
+    passable: P,
+    neighborhood: Neighborhood3D,
+}
+
+impl<T, P> SparseGrid3DGraph<'_, T, P> {
+    pub fn neighborhood(self, neighborhood: Neighborhood3D) -> Self {
+        SparseGrid3DGraph { neighborhood, ..self }
+    }
+}
+
+impl<T, P> UnweightedGraph for SparseGrid3DGraph<'_, T, P>
+where
+    P: Fn(Option<&T>) -> bool,
+{
+    type Node = Vec3D;
+
+    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec3D) -> impl Iterator<Item = Vec3D> + 'a {
+        node.neighbors(self.neighborhood)
+            .filter(|pos| self.bounds.contains(pos) && (self.passable)(self.grid.get(*pos)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::{graphs::floodfill, Input};
+
+    use super::*;
+
+    #[test]
+    fn lava_droplet() {
+        let cubes: SparseGrid3D<_> = Input::from(
+            "
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5",
+        )
+        .lines()
+        .map(|line| (line.parse::<Vec3D>().unwrap(), ()))
+        .collect();
+
+        let bounds = cubes.bounding_box().unwrap();
+        assert_eq!(bounds, Box3D::new((1, 1, 1), (3, 3, 6)));
+        let surface = cubes
+            .keys()
+            .flat_map(|cube| cube.neighbors(Neighborhood3D::Faces))
+            .filter(|pos| !cubes.contains_key(pos))
+            .count();
+        assert_eq!(surface, 64);
+
+        let outside = bounds.extend_border(1);
+        let steam = floodfill(&cubes.graph(outside.clone(), |cell| cell.is_none()), outside.lower);
+        let exterior_surface = cubes
+            .keys()
+            .flat_map(|cube| cube.neighbors(Neighborhood3D::Faces))
+            .filter(|pos| steam.contains_key(pos))
+            .count();
+        assert_eq!(exterior_surface, 58);
+
+        let layer =
+            cubes.layer(Axis::Z, 5).into_inner().into_iter().map(|c| c.is_some()).collect_vec();
+        assert_eq!(layer, [false, true, false, true, false, true, false, true, false]);
+        assert_eq!(cubes.layer(Axis::X, 1).iter().filter(|(_, c)| c.is_some()).count(), 2);
+        assert_eq!(cubes.neighbors(&Vec3D::new(2, 2, 2), Neighborhood3D::All).count(), 6);
+    }
+}